@@ -0,0 +1,68 @@
+use oauth2::{AuthUrl, ClientId, ClientSecret, TokenUrl, basic::BasicClient};
+
+use crate::auth::{TwitterAuth, TwitterToken, oauth_error};
+use crate::config::ConsumerCredentials;
+use crate::error::{SdkError, SdkResult};
+
+impl TwitterAuth {
+    /// Obtains an app-only bearer token (OAuth 2.0 client credentials).
+    /// The returned `access_token` can be passed to `TwitterClient::new` or
+    /// `RusxGateway::with_token` for endpoints that do not need a user context.
+    /// Reference: https://docs.x.com/resources/fundamentals/authentication/oauth-2-0/application-only
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "rusx.oauth.app_only_token", skip_all, err)
+    )]
+    pub async fn app_only_token(&self, consumer: &ConsumerCredentials) -> SdkResult<TwitterToken> {
+        let auth_url = AuthUrl::new(self.config.oauth_url.clone())
+            .map_err(|e| SdkError::AuthConfiguration(e.to_string()))?;
+
+        let token_url = TokenUrl::new(self.config.app_token_url())
+            .map_err(|e| SdkError::AuthConfiguration(e.to_string()))?;
+
+        let client = BasicClient::new(
+            ClientId::new(consumer.api_key.clone()),
+            Some(ClientSecret::new(consumer.api_secret.clone())),
+            auth_url,
+            Some(token_url),
+        );
+
+        let result = client
+            .exchange_client_credentials()
+            .request_async(|request| self.send_oauth_request(request))
+            .await
+            .map_err(oauth_error)?;
+
+        Ok(TwitterToken::from_response(&result))
+    }
+
+    /// Invalidates an app-only bearer token so it can no longer be used.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "rusx.oauth.invalidate_app_token", skip_all, err)
+    )]
+    pub async fn invalidate_app_token(
+        &self,
+        consumer: &ConsumerCredentials,
+        access_token: &str,
+    ) -> SdkResult<()> {
+        let response = self
+            .http
+            .post(self.config.invalidate_token_url())
+            .basic_auth(&consumer.api_key, Some(&consumer.api_secret))
+            .form(&[("access_token", access_token)])
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(SdkError::AuthConfiguration(format!(
+                "Failed to invalidate app token ({}): {}",
+                status, body
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use reqwest::Url;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinSet;
+
+use crate::auth::{AuthSession, Scope, TwitterAuth, TwitterCallbackParams, TwitterToken};
+use crate::error::{SdkError, SdkResult};
+
+/// Upper bound for the callback request head; anything larger is not a browser redirect
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// How long a connection may take to send its request head before it is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A PKCE authorization whose callback is caught by a temporary HTTP listener on the
+/// configured loopback `callback_url` (e.g. `http://127.0.0.1:3000/callback`), for CLI
+/// and desktop apps that cannot host a web backend.
+#[derive(Debug)]
+pub struct LoopbackFlow {
+    auth: TwitterAuth,
+    listener: TcpListener,
+    callback_path: String,
+    auth_url: String,
+    session: AuthSession,
+}
+
+impl TwitterAuth {
+    /// Binds the listener for `callback_url` and prepares the authorization URL.
+    /// Open `LoopbackFlow::auth_url` in the browser, then `wait` for the callback.
+    pub async fn start_loopback(&self, scopes: &[Scope]) -> SdkResult<LoopbackFlow> {
+        let callback = Url::parse(&self.callback_url)
+            .map_err(|e| SdkError::AuthConfiguration(e.to_string()))?;
+
+        let host = match callback.host_str() {
+            Some("localhost") => "127.0.0.1",
+            Some(host @ ("127.0.0.1" | "[::1]")) => host,
+            _ => {
+                return Err(SdkError::AuthConfiguration(format!(
+                    "callback_url must point to a loopback address, got {}",
+                    self.callback_url
+                )));
+            }
+        };
+        if callback.scheme() != "http" {
+            return Err(SdkError::AuthConfiguration(
+                "Loopback callback_url must use http".to_string(),
+            ));
+        }
+        let port = callback.port_or_known_default().unwrap_or(80);
+
+        let listener = TcpListener::bind(format!("{}:{}", host, port))
+            .await
+            .map_err(|e| SdkError::AuthConfiguration(format!("Cannot bind {}: {}", callback, e)))?;
+
+        let (auth_url, session) = self.start_session(scopes);
+
+        Ok(LoopbackFlow {
+            auth: self.clone(),
+            listener,
+            callback_path: callback.path().to_string(),
+            auth_url,
+            session,
+        })
+    }
+
+    /// Runs the whole loopback flow: `open_url` receives the authorization URL (print it
+    /// or launch a browser), and the token is returned once the callback arrives.
+    pub async fn authorize_loopback(
+        &self,
+        scopes: &[Scope],
+        timeout: Duration,
+        open_url: impl FnOnce(&str),
+    ) -> SdkResult<TwitterToken> {
+        let flow = self.start_loopback(scopes).await?;
+        open_url(flow.auth_url());
+        flow.wait(timeout).await
+    }
+}
+
+impl LoopbackFlow {
+    pub fn auth_url(&self) -> &str {
+        &self.auth_url
+    }
+
+    /// The address the listener is bound to.
+    pub fn local_addr(&self) -> SdkResult<std::net::SocketAddr> {
+        self.listener
+            .local_addr()
+            .map_err(|e| SdkError::AuthConfiguration(e.to_string()))
+    }
+
+    /// Waits for the browser redirect, validates `state` and exchanges the `code`.
+    pub async fn wait(self, timeout: Duration) -> SdkResult<TwitterToken> {
+        // A callback that arrives within `timeout` must not be rejected as expired
+        let ttl = AuthSession::DEFAULT_TTL.max(self.session.age() + timeout);
+
+        let params = tokio::time::timeout(timeout, self.accept_callback())
+            .await
+            .map_err(|_| SdkError::CallbackTimeout)??;
+
+        let session = self.session.with_ttl(ttl);
+        self.auth.exchange_session(params, session).await
+    }
+
+    async fn accept_callback(&self) -> SdkResult<TwitterCallbackParams> {
+        // Browsers open speculative connections that may never send a request, so each
+        // one is read in its own task and an idle socket can't hold up the callback
+        let mut pending = JoinSet::new();
+
+        loop {
+            let (mut stream, target) = tokio::select! {
+                accepted = self.listener.accept() => {
+                    let (stream, _) =
+                        accepted.map_err(|e| SdkError::AuthConfiguration(e.to_string()))?;
+                    pending.spawn(read_request(stream));
+                    continue;
+                }
+                Some(Ok(read)) = pending.join_next() => read,
+            };
+
+            let Some(target) = target else {
+                respond(&mut stream, "400 Bad Request", "Malformed request.").await;
+                continue;
+            };
+
+            let Ok(url) = Url::parse(&format!("http://localhost{}", target)) else {
+                respond(&mut stream, "400 Bad Request", "Malformed request.").await;
+                continue;
+            };
+
+            // Browsers also ask for /favicon.ico and the like
+            if url.path() != self.callback_path {
+                respond(&mut stream, "404 Not Found", "Not found.").await;
+                continue;
+            }
+
+            let query: HashMap<String, String> = url.query_pairs().into_owned().collect();
+
+            if let Some(error) = query.get("error") {
+                respond(&mut stream, "400 Bad Request", "Authorization was denied.").await;
+                return Err(SdkError::AuthConfiguration(format!(
+                    "Authorization denied: {}",
+                    error
+                )));
+            }
+
+            let (Some(code), Some(state)) = (query.get("code"), query.get("state")) else {
+                respond(&mut stream, "400 Bad Request", "Missing code or state.").await;
+                continue;
+            };
+
+            let params = TwitterCallbackParams {
+                code: code.clone(),
+                state: state.clone(),
+            };
+
+            if let Err(e) = self.session.verify_state(&params) {
+                respond(&mut stream, "400 Bad Request", "Invalid state.").await;
+                return Err(e);
+            }
+
+            respond(
+                &mut stream,
+                "200 OK",
+                "Authorization complete. You can close this window.",
+            )
+            .await;
+
+            return Ok(params);
+        }
+    }
+}
+
+async fn read_request(mut stream: TcpStream) -> (TcpStream, Option<String>) {
+    let target = tokio::time::timeout(READ_TIMEOUT, read_request_target(&mut stream))
+        .await
+        .ok()
+        .flatten();
+    (stream, target)
+}
+
+/// Reads the request head and returns the target of a `GET <target> HTTP/1.x` line
+async fn read_request_target(stream: &mut TcpStream) -> Option<String> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 || buf.len() + read > MAX_REQUEST_BYTES {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    }
+
+    let head = String::from_utf8_lossy(&buf);
+    let mut parts = head.lines().next()?.split_whitespace();
+
+    match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => Some(target.to_string()),
+        _ => None,
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, message: &str) {
+    let body = format!(
+        "<!DOCTYPE html><html><body><p>{}</p></body></html>",
+        message
+    );
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+
+    // The browser going away does not affect the outcome of the flow
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{OauthConfig, XConfig};
+
+    async fn start() -> LoopbackFlow {
+        let auth = TwitterAuth::with_config(
+            OauthConfig {
+                client_id: "client".to_string(),
+                client_secret: "secret".to_string(),
+                callback_url: "http://127.0.0.1:0/callback".to_string(),
+            },
+            XConfig::default(),
+        )
+        .unwrap();
+        auth.start_loopback(&[Scope::TweetRead]).await.unwrap()
+    }
+
+    async fn connect(flow: &LoopbackFlow) -> TcpStream {
+        TcpStream::connect(flow.local_addr().unwrap())
+            .await
+            .unwrap()
+    }
+
+    /// Sends `GET <target>` and returns the status line of the response
+    async fn get(flow: &LoopbackFlow, target: &str) -> String {
+        let mut stream = connect(flow).await;
+        let request = format!("GET {} HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n", target);
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response.lines().next().unwrap_or_default().to_string()
+    }
+
+    #[tokio::test]
+    async fn accepts_callback_with_matching_state() {
+        let flow = start().await;
+        let target = format!("/callback?code=abc&state={}", flow.session.state);
+
+        let (params, status) = tokio::join!(flow.accept_callback(), get(&flow, &target));
+
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        let params = params.unwrap();
+        assert_eq!(params.code, "abc");
+        assert_eq!(params.state, flow.session.state);
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_state() {
+        let flow = start().await;
+
+        let (params, status) = tokio::join!(
+            flow.accept_callback(),
+            get(&flow, "/callback?code=abc&state=forged")
+        );
+
+        assert_eq!(status, "HTTP/1.1 400 Bad Request");
+        assert!(matches!(params, Err(SdkError::CsrfMismatch)));
+    }
+
+    #[tokio::test]
+    async fn reports_denied_authorization() {
+        let flow = start().await;
+        let target = format!("/callback?error=access_denied&state={}", flow.session.state);
+
+        let (params, status) = tokio::join!(flow.accept_callback(), get(&flow, &target));
+
+        assert_eq!(status, "HTTP/1.1 400 Bad Request");
+        match params.err() {
+            Some(SdkError::AuthConfiguration(message)) => {
+                assert!(message.contains("access_denied"))
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn ignores_other_paths() {
+        let flow = start().await;
+        let target = format!("/callback?code=abc&state={}", flow.session.state);
+
+        let browser = async {
+            let favicon = get(&flow, "/favicon.ico").await;
+            (favicon, get(&flow, &target).await)
+        };
+        let (params, (favicon, status)) = tokio::join!(flow.accept_callback(), browser);
+
+        assert_eq!(favicon, "HTTP/1.1 404 Not Found");
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert!(params.is_ok());
+    }
+
+    #[tokio::test]
+    async fn idle_connection_does_not_block_callback() {
+        let flow = start().await;
+        let target = format!("/callback?code=abc&state={}", flow.session.state);
+
+        let browser = async {
+            let idle = connect(&flow).await;
+            let status = get(&flow, &target).await;
+            drop(idle);
+            status
+        };
+        let result = tokio::time::timeout(Duration::from_secs(2), async {
+            tokio::join!(flow.accept_callback(), browser)
+        })
+        .await;
+
+        let (params, status) = result.expect("callback was held up by the idle connection");
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert!(params.is_ok());
+    }
+
+    #[tokio::test]
+    async fn times_out_without_callback() {
+        let flow = start().await;
+
+        let result = flow.wait(Duration::from_millis(50)).await;
+
+        assert!(matches!(result, Err(SdkError::CallbackTimeout)));
+    }
+}
@@ -0,0 +1,408 @@
+use crate::{
+    client::DEFAULT_USER_AGENT,
+    config::{OauthConfig, XConfig},
+    error::{SdkError, SdkResult},
+    resources::AsQueryStr,
+};
+use oauth2::{
+    AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, HttpRequest, HttpResponse,
+    PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, RefreshToken, RequestTokenError,
+    Scope as OAuthScope, TokenResponse, TokenUrl,
+    basic::{BasicClient, BasicErrorResponse, BasicTokenResponse, BasicTokenType},
+};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub mod app;
+pub mod loopback;
+pub mod oauth1;
+pub mod refresh;
+pub mod scope;
+pub mod session;
+pub mod strategy;
+
+pub use loopback::LoopbackFlow;
+pub use oauth1::{OAuth1Auth, OAuth1Credentials};
+pub use refresh::{RefreshingAuth, TokenRefreshCallback};
+pub use scope::Scope;
+pub use session::AuthSession;
+pub use strategy::{AuthStrategy, BearerAuth};
+
+/// A simple struct to hold the tokens returned by Twitter
+/// Tokens serialized before `issued_at`/`expires_at` existed still deserialize;
+/// their expiry is simply unknown.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TwitterToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    /// Lifetime in seconds as reported by Twitter, relative to `issued_at`
+    pub expires_in: Option<u64>,
+    /// The scopes actually granted by the user, which may be fewer than requested
+    #[serde(default)]
+    pub scopes: Option<Vec<Scope>>,
+    /// Usually "bearer"
+    #[serde(default)]
+    pub token_type: Option<String>,
+    /// Unix timestamp (seconds) at which the token was received
+    #[serde(default)]
+    pub issued_at: Option<u64>,
+    /// Unix timestamp (seconds) after which the access token is no longer valid
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+impl TwitterToken {
+    /// The absolute expiry, falling back to `issued_at + expires_in`.
+    /// `None` means the expiry is unknown.
+    pub fn expiry(&self) -> Option<u64> {
+        self.expires_at.or_else(|| {
+            self.issued_at
+                .zip(self.expires_in)
+                .map(|(issued_at, expires_in)| issued_at + expires_in)
+        })
+    }
+
+    /// True once the access token has expired. Tokens with unknown expiry never report
+    /// expired; a 401 from the API is then the only signal.
+    pub fn is_expired(&self) -> bool {
+        self.expires_within(Duration::ZERO)
+    }
+
+    /// True if the access token expires within `window` from now (or already has).
+    pub fn expires_within(&self, window: Duration) -> bool {
+        self.expiry()
+            .is_some_and(|at| unix_now() + window.as_secs() >= at)
+    }
+
+    fn from_response(response: &BasicTokenResponse) -> Self {
+        let issued_at = unix_now();
+        let expires_in = response.expires_in().map(|d| d.as_secs());
+
+        Self {
+            access_token: response.access_token().secret().to_string(),
+            refresh_token: response.refresh_token().map(|t| t.secret().to_string()),
+            expires_in,
+            // Unknown scopes are skipped rather than failing the whole exchange
+            scopes: response.scopes().map(|scopes| {
+                scopes
+                    .iter()
+                    .filter_map(|s| s.as_str().parse::<Scope>().ok())
+                    .collect()
+            }),
+            token_type: Some(
+                match response.token_type() {
+                    BasicTokenType::Bearer => "bearer",
+                    BasicTokenType::Mac => "mac",
+                    BasicTokenType::Extension(other) => other,
+                }
+                .to_string(),
+            ),
+            issued_at: Some(issued_at),
+            expires_at: expires_in.map(|secs| issued_at + secs),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct TwitterCallbackParams {
+    pub code: String,
+    pub state: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TwitterAuth {
+    client: BasicClient,
+    client_secret: ClientSecret,
+    callback_url: String,
+    config: XConfig,
+    http: reqwest::Client,
+}
+
+impl TwitterAuth {
+    pub fn new(oauth_config: OauthConfig) -> SdkResult<Self> {
+        Self::with_config(oauth_config, XConfig::load())
+    }
+
+    /// Builds the OAuth client against explicit endpoints, e.g. a local mock server.
+    pub fn with_config(oauth_config: OauthConfig, x_config: XConfig) -> SdkResult<Self> {
+        let auth_url = AuthUrl::new(x_config.oauth_url.clone())
+            .map_err(|e| SdkError::AuthConfiguration(e.to_string()))?;
+
+        let token_url = TokenUrl::new(x_config.token_url())
+            .map_err(|e| SdkError::AuthConfiguration(e.to_string()))?;
+
+        let client_secret = ClientSecret::new(oauth_config.client_secret);
+
+        let client = BasicClient::new(
+            ClientId::new(oauth_config.client_id),
+            Some(client_secret.clone()),
+            auth_url,
+            Some(token_url),
+        )
+        .set_redirect_uri(
+            RedirectUrl::new(oauth_config.callback_url.clone())
+                .map_err(|e| SdkError::AuthConfiguration(e.to_string()))?,
+        );
+
+        // Like oauth2's own client, never follow redirects with client credentials attached
+        let http = reqwest::Client::builder()
+            .user_agent(DEFAULT_USER_AGENT)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+
+        Ok(Self {
+            client,
+            client_secret,
+            callback_url: oauth_config.callback_url,
+            config: x_config,
+            http,
+        })
+    }
+
+    /// Sends the token, refresh, revoke and app-only requests through `http`, e.g. to
+    /// set a timeout, proxy or user agent. Their bodies carry codes, verifiers and refresh
+    /// tokens, so build it with `reqwest::redirect::Policy::none()`.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    // Plugs `http` into oauth2 in place of its built-in (unconfigurable) reqwest client
+    pub(crate) async fn send_oauth_request(
+        &self,
+        request: HttpRequest,
+    ) -> Result<HttpResponse, reqwest::Error> {
+        let response = self
+            .http
+            .request(request.method, request.url.as_str())
+            .headers(request.headers)
+            .body(request.body)
+            .send()
+            .await?;
+
+        Ok(HttpResponse {
+            status_code: response.status(),
+            headers: response.headers().clone(),
+            body: response.bytes().await?.to_vec(),
+        })
+    }
+
+    /// Generates the Authorization URL, the PKCE verifier and the CSRF state for `Scope::DEFAULT`.
+    /// IMPORTANT: You must store the `PkceCodeVerifier` and `CsrfToken` securely
+    /// (e.g., session, redis) to use them in the callback step.
+    pub fn generate_auth_url(&self) -> (String, PkceCodeVerifier, CsrfToken) {
+        self.generate_auth_url_with_scopes(Scope::DEFAULT)
+    }
+
+    /// Same as `generate_auth_url`, but requests the given scopes.
+    /// Include `Scope::OfflineAccess` if you need a refresh token.
+    pub fn generate_auth_url_with_scopes(
+        &self,
+        scopes: &[Scope],
+    ) -> (String, PkceCodeVerifier, CsrfToken) {
+        let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+
+        let mut requested: Vec<Scope> = Vec::with_capacity(scopes.len());
+        for scope in scopes {
+            if !requested.contains(scope) {
+                requested.push(*scope);
+            }
+        }
+
+        let (auth_url, csrf_token) = self
+            .client
+            .authorize_url(CsrfToken::new_random)
+            .add_scopes(
+                requested
+                    .iter()
+                    .map(|s| OAuthScope::new(s.as_str().to_string())),
+            )
+            .set_pkce_challenge(pkce_challenge)
+            .url();
+
+        (auth_url.to_string(), pkce_verifier, csrf_token)
+    }
+
+    /// Starts an authorization for the given scopes and bundles everything needed on the
+    /// callback into a serializable `AuthSession`.
+    pub fn start_session(&self, scopes: &[Scope]) -> (String, AuthSession) {
+        let (url, verifier, state) = self.generate_auth_url_with_scopes(scopes);
+        let session = AuthSession::new(verifier, state, scopes.to_vec(), self.callback_url.clone());
+
+        (url, session)
+    }
+
+    /// Checks that the `state` returned on the callback matches the one issued with the
+    /// authorization URL. Call this before `exchange_code`.
+    pub fn verify_state(expected: &CsrfToken, params: &TwitterCallbackParams) -> SdkResult<()> {
+        if constant_time_eq(expected.secret().as_bytes(), params.state.as_bytes()) {
+            Ok(())
+        } else {
+            Err(SdkError::CsrfMismatch)
+        }
+    }
+
+    /// Verifies the callback `state` and exchanges its `code` for an access token.
+    pub async fn exchange_callback(
+        &self,
+        params: TwitterCallbackParams,
+        expected_state: &CsrfToken,
+        pkce_verifier: PkceCodeVerifier,
+    ) -> SdkResult<TwitterToken> {
+        Self::verify_state(expected_state, &params)?;
+        self.exchange_code(params.code, pkce_verifier).await
+    }
+
+    /// Validates a stored `AuthSession` against the callback (expiry, redirect URL and
+    /// `state`) and exchanges the code for an access token.
+    pub async fn exchange_session(
+        &self,
+        params: TwitterCallbackParams,
+        session: AuthSession,
+    ) -> SdkResult<TwitterToken> {
+        if session.is_expired() {
+            return Err(SdkError::AuthSessionExpired);
+        }
+        // The code is bound to the redirect_uri it was issued for
+        if session.redirect_url != self.callback_url {
+            return Err(SdkError::AuthConfiguration(format!(
+                "Session was started for redirect URL {}, but this client uses {}",
+                session.redirect_url, self.callback_url
+            )));
+        }
+        session.verify_state(&params)?;
+
+        let mut token = self.exchange_code(params.code, session.clone()).await?;
+        // Fall back to what was requested when the server does not echo the scopes
+        if token.scopes.is_none() {
+            token.scopes = Some(session.scopes);
+        }
+
+        Ok(token)
+    }
+
+    /// Exchanges the authorization code for an access token.
+    /// Accepts either a `PkceCodeVerifier` or a stored `AuthSession`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "rusx.oauth.exchange_code", skip_all, err)
+    )]
+    pub async fn exchange_code(
+        &self,
+        code: String,
+        pkce_verifier: impl Into<PkceCodeVerifier>,
+    ) -> SdkResult<TwitterToken> {
+        let result = self
+            .client
+            .exchange_code(AuthorizationCode::new(code))
+            .set_pkce_verifier(pkce_verifier.into())
+            .request_async(|request| self.send_oauth_request(request))
+            .await
+            .map_err(oauth_error)?;
+
+        Ok(TwitterToken::from_response(&result))
+    }
+
+    /// Exchanges a refresh token for a fresh access token.
+    /// Twitter rotates refresh tokens, so always persist the returned `refresh_token`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "rusx.oauth.refresh_token", skip_all, err)
+    )]
+    pub async fn refresh_token(&self, refresh_token: String) -> SdkResult<TwitterToken> {
+        let result = self
+            .client
+            .exchange_refresh_token(&RefreshToken::new(refresh_token.clone()))
+            .request_async(|request| self.send_oauth_request(request))
+            .await
+            .map_err(oauth_error)?;
+
+        let mut token = TwitterToken::from_response(&result);
+        // Keep the old refresh token usable if the server did not rotate it
+        if token.refresh_token.is_none() {
+            token.refresh_token = Some(refresh_token);
+        }
+
+        Ok(token)
+    }
+
+    /// Revokes an access token (`POST /2/oauth2/revoke`).
+    pub async fn revoke_access_token(&self, access_token: String) -> SdkResult<()> {
+        self.revoke(&access_token, "access_token").await
+    }
+
+    /// Revokes a refresh token, which also invalidates the access tokens issued from it.
+    pub async fn revoke_refresh_token(&self, refresh_token: String) -> SdkResult<()> {
+        self.revoke(&refresh_token, "refresh_token").await
+    }
+
+    /// Revokes both the access and (if present) the refresh token, e.g. when a user
+    /// disconnects their X account.
+    pub async fn revoke_all(&self, token: &TwitterToken) -> SdkResult<()> {
+        if let Some(refresh_token) = &token.refresh_token {
+            self.revoke_refresh_token(refresh_token.clone()).await?;
+        }
+        self.revoke_access_token(token.access_token.clone()).await
+    }
+
+    // oauth2's revocation request insists on an HTTPS endpoint, which rules out
+    // local mock servers, so the RFC 7009 request is sent by hand.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "rusx.oauth.revoke", skip_all, fields(token_type = token_type_hint), err)
+    )]
+    async fn revoke(&self, token: &str, token_type_hint: &str) -> SdkResult<()> {
+        let response = self
+            .http
+            .post(self.config.revoke_url())
+            .basic_auth(
+                self.client.client_id().as_str(),
+                Some(self.client_secret.secret()),
+            )
+            .form(&[
+                ("token", token),
+                ("token_type_hint", token_type_hint),
+                ("client_id", self.client.client_id().as_str()),
+            ])
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(SdkError::AuthConfiguration(format!(
+                "Failed to revoke {} ({}): {}",
+                token_type_hint, status, body
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Keeps the token endpoint's error code, which oauth2's own `Display` leaves out
+pub(crate) fn oauth_error(e: RequestTokenError<reqwest::Error, BasicErrorResponse>) -> SdkError {
+    match e {
+        RequestTokenError::ServerResponse(response) => SdkError::OAuth {
+            error: response.error().to_string(),
+            description: response.error_description().cloned(),
+        },
+        other => SdkError::AuthConfiguration(other.to_string()),
+    }
+}
+
+/// Compares without short-circuiting so the state cannot be guessed byte by byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,269 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use rand::distributions::Alphanumeric;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderValue};
+use reqwest::{Method, Request, Url};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+
+use crate::auth::strategy::{AuthStrategy, credential_key};
+use crate::auth::unix_now;
+use crate::config::ConsumerCredentials;
+use crate::error::{SdkError, SdkResult};
+
+/// User-context OAuth 1.0a credentials: the app's consumer key pair plus the
+/// user's access token pair.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OAuth1Credentials {
+    pub consumer: ConsumerCredentials,
+    pub access_token: String,
+    pub access_token_secret: String,
+}
+
+impl fmt::Debug for OAuth1Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuth1Credentials")
+            .field("consumer_key", &self.consumer.api_key)
+            .field("access_token", &self.access_token)
+            .field("secrets", &"[redacted]")
+            .finish()
+    }
+}
+
+/// Signs every request with OAuth 1.0a HMAC-SHA1.
+/// Reference: https://docs.x.com/resources/fundamentals/authentication/oauth-1-0a/creating-a-signature
+#[derive(Debug, Clone)]
+pub struct OAuth1Auth {
+    credentials: OAuth1Credentials,
+}
+
+impl OAuth1Auth {
+    pub fn new(credentials: OAuth1Credentials) -> Self {
+        Self { credentials }
+    }
+
+    /// Builds the `Authorization` header value for a request with an explicit nonce and
+    /// timestamp. `form_params` are the (decoded) `application/x-www-form-urlencoded`
+    /// body parameters, if any; query parameters are taken from `url`.
+    pub fn authorization_header(
+        &self,
+        method: &Method,
+        url: &Url,
+        form_params: &[(String, String)],
+        nonce: &str,
+        timestamp: u64,
+    ) -> String {
+        let timestamp = timestamp.to_string();
+        let mut oauth_params = vec![
+            (
+                "oauth_consumer_key",
+                self.credentials.consumer.api_key.as_str(),
+            ),
+            ("oauth_nonce", nonce),
+            ("oauth_signature_method", "HMAC-SHA1"),
+            ("oauth_timestamp", timestamp.as_str()),
+            ("oauth_token", self.credentials.access_token.as_str()),
+            ("oauth_version", "1.0"),
+        ];
+
+        let signature = self.signature(method, url, form_params, &oauth_params);
+        oauth_params.push(("oauth_signature", signature.as_str()));
+        oauth_params.sort();
+
+        let fields = oauth_params
+            .iter()
+            .map(|(k, v)| format!("{}=\"{}\"", encode(k), encode(v)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("OAuth {}", fields)
+    }
+
+    fn signature(
+        &self,
+        method: &Method,
+        url: &Url,
+        form_params: &[(String, String)],
+        oauth_params: &[(&str, &str)],
+    ) -> String {
+        let mut params: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(k, v)| (encode(&k), encode(&v)))
+            .chain(form_params.iter().map(|(k, v)| (encode(k), encode(v))))
+            .chain(oauth_params.iter().map(|(k, v)| (encode(k), encode(v))))
+            .collect();
+        params.sort();
+
+        let param_string = params
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let base_string = format!(
+            "{}&{}&{}",
+            method.as_str().to_uppercase(),
+            encode(&base_url(url)),
+            encode(&param_string)
+        );
+
+        let signing_key = format!(
+            "{}&{}",
+            encode(&self.credentials.consumer.api_secret),
+            encode(&self.credentials.access_token_secret)
+        );
+
+        // HMAC accepts keys of any length, so this cannot fail
+        let mut mac = Hmac::<Sha1>::new_from_slice(signing_key.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(base_string.as_bytes());
+
+        BASE64.encode(mac.finalize().into_bytes())
+    }
+}
+
+#[async_trait]
+impl AuthStrategy for OAuth1Auth {
+    async fn authorize(&self, request: &mut Request) -> SdkResult<()> {
+        let form_params = form_body_params(request);
+        let nonce: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+
+        let header = self.authorization_header(
+            request.method(),
+            request.url(),
+            &form_params,
+            &nonce,
+            unix_now(),
+        );
+
+        let mut value = HeaderValue::from_str(&header)
+            .map_err(|e| SdkError::AuthConfiguration(e.to_string()))?;
+        value.set_sensitive(true);
+
+        request.headers_mut().insert(AUTHORIZATION, value);
+        Ok(())
+    }
+
+    fn rate_limit_key(&self) -> u64 {
+        credential_key(&self.credentials.access_token)
+    }
+}
+
+/// Only url-encoded form bodies take part in the signature (not JSON or multipart)
+fn form_body_params(request: &Request) -> Vec<(String, String)> {
+    let is_form = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/x-www-form-urlencoded"));
+
+    match request.body().and_then(|b| b.as_bytes()) {
+        Some(bytes) if is_form => url::form_urlencoded::parse(bytes).into_owned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Scheme, host, non-default port and path, without query or fragment
+fn base_url(url: &Url) -> String {
+    let mut base = format!(
+        "{}://{}",
+        url.scheme(),
+        url.host_str().unwrap_or_default().to_lowercase()
+    );
+    if let Some(port) = url.port() {
+        base.push_str(&format!(":{}", port));
+    }
+    base.push_str(url.path());
+    base
+}
+
+/// RFC 3986 percent-encoding, leaving only unreserved characters as-is
+fn encode(value: &str) -> String {
+    urlencoding::encode(value).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // https://docs.x.com/resources/fundamentals/authentication/oauth-1-0a/creating-a-signature
+    fn published_example() -> OAuth1Auth {
+        OAuth1Auth::new(OAuth1Credentials {
+            consumer: ConsumerCredentials {
+                api_key: "xvz1evFS4wEEPTGEFPHBog".to_string(),
+                api_secret: "kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw".to_string(),
+            },
+            access_token: "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb".to_string(),
+            access_token_secret: "LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE".to_string(),
+        })
+    }
+
+    #[test]
+    fn matches_published_signature_vector() {
+        let url =
+            Url::parse("https://api.twitter.com/1.1/statuses/update.json?include_entities=true")
+                .unwrap();
+        let form = vec![(
+            "status".to_string(),
+            "Hello Ladies + Gentlemen, a signed OAuth request!".to_string(),
+        )];
+
+        let header = published_example().authorization_header(
+            &Method::POST,
+            &url,
+            &form,
+            "kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg",
+            1318622958,
+        );
+
+        assert!(header.starts_with("OAuth "));
+        assert!(header.contains(r#"oauth_signature="hCtSmYh%2BiHYCEqBWrE7C7hYmtUk%3D""#));
+        assert!(header.contains(r#"oauth_nonce="kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg""#));
+        assert!(header.contains(r#"oauth_timestamp="1318622958""#));
+    }
+
+    #[test]
+    fn signs_urlencoded_form_bodies_only() {
+        let url = Url::parse("https://api.twitter.com/1.1/statuses/update.json").unwrap();
+        let client = reqwest::Client::new();
+
+        let form = client
+            .post(url.clone())
+            .form(&[(
+                "status",
+                "Hello Ladies + Gentlemen, a signed OAuth request!",
+            )])
+            .build()
+            .unwrap();
+        assert_eq!(
+            form_body_params(&form),
+            vec![(
+                "status".to_string(),
+                "Hello Ladies + Gentlemen, a signed OAuth request!".to_string()
+            )]
+        );
+
+        let json = client
+            .post(url)
+            .json(&serde_json::json!({ "text": "hi" }))
+            .build()
+            .unwrap();
+        assert!(form_body_params(&json).is_empty());
+    }
+
+    #[test]
+    fn debug_redacts_secrets() {
+        let debug = format!("{:?}", published_example());
+        assert!(!debug.contains("LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE"));
+        assert!(!debug.contains("kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw"));
+    }
+}
@@ -0,0 +1,469 @@
+use std::fmt;
+use std::future::Future;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Request;
+use reqwest::header::AUTHORIZATION;
+use tokio::sync::Mutex;
+
+use crate::auth::strategy::{AuthStrategy, BearerAuth, credential_key};
+use crate::auth::{TwitterAuth, TwitterToken, unix_now};
+use crate::error::{SdkError, SdkResult};
+use crate::store::TokenStore;
+
+/// Invoked with the new token every time `RefreshingAuth` refreshes, so it can be persisted.
+/// It is awaited before the new token is used; see `RefreshingAuth::on_refresh`.
+pub type TokenRefreshCallback =
+    Arc<dyn Fn(TwitterToken) -> Pin<Box<dyn Future<Output = SdkResult<()>> + Send>> + Send + Sync>;
+
+/// Bearer auth backed by a full `TwitterToken` that refreshes itself shortly before it
+/// expires, and once more if the API still answers 401. Concurrent requests wait on the
+/// same refresh instead of racing (Twitter rotates refresh tokens, so a second refresh
+/// with the old one would fail).
+pub struct RefreshingAuth {
+    auth: TwitterAuth,
+    state: Mutex<TokenState>,
+    refresh_margin: Duration,
+    on_refresh: Option<TokenRefreshCallback>,
+    // Where the token is shared with other processes, see `persist_to`
+    source: Option<(Arc<dyn TokenStore>, String)>,
+    // Fixed at construction so refreshes do not reset the rate-limit budget
+    rate_limit_key: u64,
+}
+
+impl RefreshingAuth {
+    pub const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+    /// Uses the token's own expiry. Tokens stored before expiry tracking existed are
+    /// assumed to have been issued just now; use `expires_at` to correct that.
+    pub fn new(auth: TwitterAuth, token: TwitterToken) -> Self {
+        let token = with_expiry(token);
+
+        Self {
+            auth,
+            rate_limit_key: credential_key(&token.access_token),
+            state: Mutex::new(TokenState {
+                token,
+                unpersisted: false,
+            }),
+            refresh_margin: Self::DEFAULT_REFRESH_MARGIN,
+            on_refresh: None,
+            source: None,
+        }
+    }
+
+    /// Keys client-side rate limiting by a stable identity (e.g. the account id)
+    /// instead of the initial access token.
+    pub fn rate_limit_identity(mut self, identity: impl Hash) -> Self {
+        self.rate_limit_key = credential_key(identity);
+        self
+    }
+
+    /// Sets the absolute expiry (unix seconds) of the current access token.
+    pub fn expires_at(mut self, expires_at: u64) -> Self {
+        self.state.get_mut().token.expires_at = Some(expires_at);
+        self
+    }
+
+    /// How long before expiry the token is refreshed. Defaults to one minute.
+    pub fn refresh_margin(mut self, margin: Duration) -> Self {
+        self.refresh_margin = margin;
+        self
+    }
+
+    /// Persists every refreshed token. Twitter rotates refresh tokens, so losing one
+    /// means the user has to reconnect: the callback is awaited (with concurrent requests
+    /// waiting) before the new token is used, and if it fails the request fails with its
+    /// error and the write is retried on the next request.
+    pub fn on_refresh<F, Fut>(mut self, callback: F) -> Self
+    where
+        F: Fn(TwitterToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = SdkResult<()>> + Send + 'static,
+    {
+        self.on_refresh = Some(Arc::new(move |token| Box::pin(callback(token))));
+        self
+    }
+
+    /// Shorthand for `on_refresh` writing to a `TokenStore` under `account_id`.
+    /// The store is also read back before each refresh (and after a rejected one), so
+    /// processes sharing it pick up a token another one already rotated.
+    pub fn persist_to(mut self, store: Arc<dyn TokenStore>, account_id: impl Into<String>) -> Self {
+        let account_id = account_id.into();
+        self.source = Some((store.clone(), account_id.clone()));
+        self.on_refresh(move |token| {
+            let store = store.clone();
+            let account_id = account_id.clone();
+            async move { store.put(&account_id, token).await }
+        })
+    }
+
+    /// The token currently in use, including any refreshes that happened so far.
+    pub async fn token(&self) -> TwitterToken {
+        self.state.lock().await.token.clone()
+    }
+
+    async fn refresh(&self, state: &mut TokenState) -> SdkResult<()> {
+        // Another process sharing the store may have refreshed already
+        if self.reload(state).await? {
+            return Ok(());
+        }
+
+        match self.refresh_token(state).await {
+            // ...or did so meanwhile, rotating the refresh token this one just sent
+            Err(e @ SdkError::OAuth { .. }) => {
+                let rejected = state.token.refresh_token.clone();
+                if self.reload(state).await? {
+                    return Ok(());
+                }
+                if state.token.refresh_token == rejected {
+                    return Err(e);
+                }
+                self.refresh_token(state).await
+            }
+            result => result,
+        }
+    }
+
+    async fn refresh_token(&self, state: &mut TokenState) -> SdkResult<()> {
+        let refresh_token = state.token.refresh_token.clone().ok_or_else(|| {
+            SdkError::AuthConfiguration("Token expired and has no refresh_token".to_string())
+        })?;
+
+        state.token = self.auth.refresh_token(refresh_token).await?;
+        state.unpersisted = self.on_refresh.is_some();
+
+        Ok(())
+    }
+
+    /// Adopts the stored token if it is not the one in use. Returns true if it is fresh
+    /// enough to use as is; a stale one still brings its (newer) refresh token.
+    async fn reload(&self, state: &mut TokenState) -> SdkResult<bool> {
+        // An unpersisted refresh is newer than anything in the store
+        let (Some((store, account_id)), false) = (&self.source, state.unpersisted) else {
+            return Ok(false);
+        };
+        let Some(stored) = store.get(account_id).await? else {
+            return Ok(false);
+        };
+        if stored.access_token == state.token.access_token
+            && stored.refresh_token == state.token.refresh_token
+        {
+            return Ok(false);
+        }
+
+        state.token = with_expiry(stored);
+        Ok(!state.token.expires_within(self.refresh_margin))
+    }
+
+    /// Runs `on_refresh` for a refreshed token that has not been persisted yet
+    async fn persist(&self, state: &mut TokenState) -> SdkResult<()> {
+        if let (true, Some(callback)) = (state.unpersisted, &self.on_refresh) {
+            callback(state.token.clone()).await?;
+            state.unpersisted = false;
+        }
+
+        Ok(())
+    }
+}
+
+/// Tokens stored before expiry tracking existed are assumed to have been issued just now
+fn with_expiry(mut token: TwitterToken) -> TwitterToken {
+    if token.expiry().is_none() {
+        token.expires_at = token.expires_in.map(|secs| unix_now() + secs);
+    }
+    token
+}
+
+struct TokenState {
+    token: TwitterToken,
+    /// Set when a refresh succeeded but `on_refresh` has not (yet) succeeded for it
+    unpersisted: bool,
+}
+
+#[async_trait]
+impl AuthStrategy for RefreshingAuth {
+    async fn authorize(&self, request: &mut Request) -> SdkResult<()> {
+        let mut state = self.state.lock().await;
+
+        if state.token.expires_within(self.refresh_margin) && state.token.refresh_token.is_some() {
+            // A failed early refresh is fine as long as the current token still works
+            if let Err(e) = self.refresh(&mut state).await
+                && state.token.is_expired()
+            {
+                return Err(e);
+            }
+        }
+        self.persist(&mut state).await?;
+
+        BearerAuth::new(state.token.access_token.clone())
+            .authorize(request)
+            .await
+    }
+
+    async fn on_unauthorized(&self, request: &Request) -> SdkResult<bool> {
+        let mut state = self.state.lock().await;
+
+        let used = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+
+        // Another request already refreshed while this one was in flight
+        if used != Some(state.token.access_token.as_str()) {
+            return Ok(true);
+        }
+
+        if state.token.refresh_token.is_none() {
+            return Ok(false);
+        }
+
+        self.refresh(&mut state).await?;
+        self.persist(&mut state).await?;
+        Ok(true)
+    }
+
+    fn rate_limit_key(&self) -> u64 {
+        self.rate_limit_key
+    }
+}
+
+impl fmt::Debug for RefreshingAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RefreshingAuth")
+            .field("auth", &self.auth)
+            .field("refresh_margin", &self.refresh_margin)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use reqwest::Method;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+    use crate::TwitterClient;
+    use crate::config::{OauthConfig, XConfig};
+
+    /// A loopback token endpoint that rotates refresh tokens like X does, plus a
+    /// `/2/users/me` that rejects `OLD` and `REVOKED` with a 401.
+    #[derive(Default)]
+    struct Server {
+        refreshes: AtomicUsize,
+        api_calls: AtomicUsize,
+        used_refresh_tokens: std::sync::Mutex<HashSet<String>>,
+    }
+
+    impl Server {
+        async fn start() -> (Arc<Self>, SocketAddr) {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let server = Arc::new(Self::default());
+
+            let shared = server.clone();
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(shared.clone().handle(stream));
+                }
+            });
+
+            (server, addr)
+        }
+
+        async fn handle(self: Arc<Self>, mut stream: TcpStream) {
+            let (head, body) = read_request(&mut stream).await;
+            let (status, json) = if head.starts_with("POST /2/oauth2/token") {
+                self.refresh(&body).await
+            } else {
+                self.api_calls.fetch_add(1, Ordering::SeqCst);
+                let head = head.to_ascii_lowercase();
+                if head.contains("bearer old") || head.contains("bearer revoked") {
+                    ("401 Unauthorized", r#"{"title":"Unauthorized","type":"about:blank","status":401,"detail":"Unauthorized"}"#.to_string())
+                } else {
+                    (
+                        "200 OK",
+                        r#"{"data":{"id":"1","name":"n","username":"u"}}"#.to_string(),
+                    )
+                }
+            };
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                json.len(),
+                json
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+
+        async fn refresh(&self, body: &str) -> (&'static str, String) {
+            let refresh_token = url::form_urlencoded::parse(body.as_bytes())
+                .find(|(k, _)| k == "refresh_token")
+                .map(|(_, v)| v.into_owned())
+                .unwrap_or_default();
+
+            // Slow enough for concurrent requests to pile up behind the refresh
+            tokio::time::sleep(Duration::from_millis(50)).await;
+
+            if !self
+                .used_refresh_tokens
+                .lock()
+                .unwrap()
+                .insert(refresh_token)
+            {
+                let error = r#"{"error":"invalid_request","error_description":"Value passed for the token was invalid."}"#;
+                return ("400 Bad Request", error.to_string());
+            }
+
+            let n = self.refreshes.fetch_add(1, Ordering::SeqCst) + 1;
+            let token = format!(
+                r#"{{"token_type":"bearer","access_token":"A{}","refresh_token":"R{}","expires_in":7200}}"#,
+                n,
+                n + 1
+            );
+            ("200 OK", token)
+        }
+    }
+
+    async fn read_request(stream: &mut TcpStream) -> (String, String) {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 1024];
+
+        loop {
+            let read = stream.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..read]);
+
+            let text = String::from_utf8_lossy(&buf).into_owned();
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|line| {
+                        line.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse().unwrap())
+                    })
+                    .unwrap_or(0);
+                if body.len() >= length || read == 0 {
+                    return (head.to_string(), body.to_string());
+                }
+            }
+            if read == 0 {
+                return (String::from_utf8_lossy(&buf).into_owned(), String::new());
+            }
+        }
+    }
+
+    fn config(addr: SocketAddr) -> XConfig {
+        XConfig::default().with_api_base(format!("http://{}/2", addr))
+    }
+
+    fn auth(addr: SocketAddr) -> TwitterAuth {
+        let oauth = OauthConfig {
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            callback_url: "http://127.0.0.1/callback".to_string(),
+        };
+        TwitterAuth::with_config(oauth, config(addr)).unwrap()
+    }
+
+    fn token(access_token: &str, expires_at: u64) -> TwitterToken {
+        TwitterToken {
+            access_token: access_token.to_string(),
+            refresh_token: Some("R1".to_string()),
+            expires_at: Some(expires_at),
+            ..Default::default()
+        }
+    }
+
+    fn client(addr: SocketAddr, auth: Arc<RefreshingAuth>) -> TwitterClient {
+        TwitterClient::builder()
+            .config(config(addr))
+            .shared_auth(auth)
+            .build()
+    }
+
+    async fn get_me(client: &TwitterClient) -> SdkResult<()> {
+        client
+            .send::<serde_json::Value>(Method::GET, "/users/me")
+            .await
+            .map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_share_one_refresh() {
+        let (server, addr) = Server::start().await;
+        let refreshing = Arc::new(RefreshingAuth::new(auth(addr), token("OLD", 1)));
+        let client = client(addr, refreshing.clone());
+
+        let mut requests = tokio::task::JoinSet::new();
+        for _ in 0..8 {
+            let client = client.clone();
+            requests.spawn(async move { get_me(&client).await });
+        }
+        while let Some(result) = requests.join_next().await {
+            result.unwrap().unwrap();
+        }
+
+        assert_eq!(server.refreshes.load(Ordering::SeqCst), 1);
+        assert_eq!(refreshing.token().await.access_token, "A1");
+    }
+
+    #[tokio::test]
+    async fn replays_once_after_a_401() {
+        let (server, addr) = Server::start().await;
+        // Not expired as far as the client knows, but revoked on the server
+        let refreshing = Arc::new(RefreshingAuth::new(
+            auth(addr),
+            token("REVOKED", unix_now() + 3600),
+        ));
+
+        get_me(&client(addr, refreshing.clone())).await.unwrap();
+
+        assert_eq!(server.refreshes.load(Ordering::SeqCst), 1);
+        assert_eq!(server.api_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn retries_a_failed_persist_without_refreshing_again() {
+        let (server, addr) = Server::start().await;
+        let persisted = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let attempts = Arc::new(AtomicUsize::new(0));
+
+        let (log, counter) = (persisted.clone(), attempts.clone());
+        let refreshing =
+            RefreshingAuth::new(auth(addr), token("OLD", 1)).on_refresh(move |token| {
+                let (log, counter) = (log.clone(), counter.clone());
+                async move {
+                    if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return Err(SdkError::TokenStore("disk full".to_string()));
+                    }
+                    log.lock().unwrap().push(token.access_token);
+                    Ok(())
+                }
+            });
+        let client = client(addr, Arc::new(refreshing));
+
+        // The refreshed token is not used until it has been persisted
+        assert!(matches!(
+            get_me(&client).await,
+            Err(SdkError::TokenStore(_))
+        ));
+        assert_eq!(server.api_calls.load(Ordering::SeqCst), 0);
+
+        get_me(&client).await.unwrap();
+
+        assert_eq!(server.refreshes.load(Ordering::SeqCst), 1);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(*persisted.lock().unwrap(), ["A1"]);
+    }
+}
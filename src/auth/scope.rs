@@ -0,0 +1,156 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::SdkError;
+use crate::resources::AsQueryStr;
+
+/// OAuth 2.0 scopes supported by the X API v2
+/// Reference: https://docs.x.com/resources/fundamentals/authentication/oauth-2-0/authorization-code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Scope {
+    #[serde(rename = "tweet.read")]
+    TweetRead,
+
+    #[serde(rename = "tweet.write")]
+    TweetWrite,
+
+    #[serde(rename = "tweet.moderate.write")]
+    TweetModerateWrite,
+
+    #[serde(rename = "users.email")]
+    UsersEmail,
+
+    #[serde(rename = "users.read")]
+    UsersRead,
+
+    #[serde(rename = "follows.read")]
+    FollowsRead,
+
+    #[serde(rename = "follows.write")]
+    FollowsWrite,
+
+    #[serde(rename = "offline.access")]
+    OfflineAccess,
+
+    #[serde(rename = "space.read")]
+    SpaceRead,
+
+    #[serde(rename = "mute.read")]
+    MuteRead,
+
+    #[serde(rename = "mute.write")]
+    MuteWrite,
+
+    #[serde(rename = "like.read")]
+    LikeRead,
+
+    #[serde(rename = "like.write")]
+    LikeWrite,
+
+    #[serde(rename = "list.read")]
+    ListRead,
+
+    #[serde(rename = "list.write")]
+    ListWrite,
+
+    #[serde(rename = "block.read")]
+    BlockRead,
+
+    #[serde(rename = "block.write")]
+    BlockWrite,
+
+    #[serde(rename = "bookmark.read")]
+    BookmarkRead,
+
+    #[serde(rename = "bookmark.write")]
+    BookmarkWrite,
+
+    #[serde(rename = "dm.read")]
+    DmRead,
+
+    #[serde(rename = "dm.write")]
+    DmWrite,
+
+    #[serde(rename = "media.write")]
+    MediaWrite,
+}
+
+impl Scope {
+    pub const ALL: &'static [Scope] = &[
+        Self::TweetRead,
+        Self::TweetWrite,
+        Self::TweetModerateWrite,
+        Self::UsersEmail,
+        Self::UsersRead,
+        Self::FollowsRead,
+        Self::FollowsWrite,
+        Self::OfflineAccess,
+        Self::SpaceRead,
+        Self::MuteRead,
+        Self::MuteWrite,
+        Self::LikeRead,
+        Self::LikeWrite,
+        Self::ListRead,
+        Self::ListWrite,
+        Self::BlockRead,
+        Self::BlockWrite,
+        Self::BookmarkRead,
+        Self::BookmarkWrite,
+        Self::DmRead,
+        Self::DmWrite,
+        Self::MediaWrite,
+    ];
+
+    /// The scopes requested by `TwitterAuth::generate_auth_url`.
+    /// Both tweet.read and users.read are mandatory for most endpoints.
+    pub const DEFAULT: &'static [Scope] = &[Self::TweetRead, Self::UsersRead, Self::OfflineAccess];
+}
+
+impl AsQueryStr for Scope {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::TweetRead => "tweet.read",
+            Self::TweetWrite => "tweet.write",
+            Self::TweetModerateWrite => "tweet.moderate.write",
+            Self::UsersEmail => "users.email",
+            Self::UsersRead => "users.read",
+            Self::FollowsRead => "follows.read",
+            Self::FollowsWrite => "follows.write",
+            Self::OfflineAccess => "offline.access",
+            Self::SpaceRead => "space.read",
+            Self::MuteRead => "mute.read",
+            Self::MuteWrite => "mute.write",
+            Self::LikeRead => "like.read",
+            Self::LikeWrite => "like.write",
+            Self::ListRead => "list.read",
+            Self::ListWrite => "list.write",
+            Self::BlockRead => "block.read",
+            Self::BlockWrite => "block.write",
+            Self::BookmarkRead => "bookmark.read",
+            Self::BookmarkWrite => "bookmark.write",
+            Self::DmRead => "dm.read",
+            Self::DmWrite => "dm.write",
+            Self::MediaWrite => "media.write",
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Scope {
+    type Err = SdkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|scope| scope.as_str() == s)
+            .ok_or_else(|| SdkError::AuthConfiguration(format!("Unknown OAuth scope: {}", s)))
+    }
+}
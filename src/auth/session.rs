@@ -0,0 +1,98 @@
+use std::fmt;
+use std::time::Duration;
+
+use oauth2::{CsrfToken, PkceCodeVerifier};
+use serde::{Deserialize, Serialize};
+
+use crate::auth::{Scope, TwitterAuth, TwitterCallbackParams, unix_now};
+use crate::error::SdkResult;
+
+/// Everything needed to finish a PKCE authorization once the user comes back
+/// on the callback. Serialize it into your session store (cookie, redis, ...)
+/// between the redirect and the callback.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AuthSession {
+    pub pkce_verifier: String,
+    pub state: String,
+    pub scopes: Vec<Scope>,
+    /// Unix timestamp (seconds) at which the authorization URL was generated
+    pub created_at: u64,
+    /// The `redirect_uri` the authorization was started with
+    pub redirect_url: String,
+    /// How long after `created_at` the session is accepted by `TwitterAuth::exchange_session`
+    #[serde(default = "default_ttl_secs")]
+    pub ttl_secs: u64,
+}
+
+fn default_ttl_secs() -> u64 {
+    AuthSession::DEFAULT_TTL.as_secs()
+}
+
+impl AuthSession {
+    /// Default lifetime of a session, see `with_ttl`
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(10 * 60);
+
+    pub fn new(
+        pkce_verifier: PkceCodeVerifier,
+        state: CsrfToken,
+        scopes: Vec<Scope>,
+        redirect_url: String,
+    ) -> Self {
+        Self {
+            pkce_verifier: pkce_verifier.secret().to_string(),
+            state: state.secret().to_string(),
+            scopes,
+            created_at: unix_now(),
+            redirect_url,
+            ttl_secs: default_ttl_secs(),
+        }
+    }
+
+    /// Accepts the callback for longer (or shorter) than `DEFAULT_TTL`.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl_secs = ttl.as_secs();
+        self
+    }
+
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs)
+    }
+
+    /// Time elapsed since the authorization URL was generated
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(unix_now().saturating_sub(self.created_at))
+    }
+
+    /// True once the session is older than its `ttl`
+    pub fn is_expired(&self) -> bool {
+        self.age() > self.ttl()
+    }
+
+    pub fn csrf_token(&self) -> CsrfToken {
+        CsrfToken::new(self.state.clone())
+    }
+
+    pub fn verify_state(&self, params: &TwitterCallbackParams) -> SdkResult<()> {
+        TwitterAuth::verify_state(&self.csrf_token(), params)
+    }
+}
+
+impl From<AuthSession> for PkceCodeVerifier {
+    fn from(session: AuthSession) -> Self {
+        PkceCodeVerifier::new(session.pkce_verifier)
+    }
+}
+
+// Keep the verifier and state out of logs
+impl fmt::Debug for AuthSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthSession")
+            .field("pkce_verifier", &"[redacted]")
+            .field("state", &"[redacted]")
+            .field("scopes", &self.scopes)
+            .field("created_at", &self.created_at)
+            .field("redirect_url", &self.redirect_url)
+            .field("ttl_secs", &self.ttl_secs)
+            .finish()
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use async_trait::async_trait;
+use reqwest::Request;
+use reqwest::header::{AUTHORIZATION, HeaderValue};
+
+use crate::error::{SdkError, SdkResult};
+
+/// How `TwitterClient` authenticates each outgoing request.
+#[async_trait]
+pub trait AuthStrategy: fmt::Debug + Send + Sync {
+    /// Adds credentials to a fully built request right before it is sent.
+    async fn authorize(&self, request: &mut Request) -> SdkResult<()>;
+
+    /// Called when the API rejected `request` with a 401. Returns `true` if the
+    /// credentials were renewed and the request should be sent once more.
+    async fn on_unauthorized(&self, _request: &Request) -> SdkResult<bool> {
+        Ok(false)
+    }
+
+    /// Identifies the credential for client-side rate limiting, since X counts
+    /// requests per token. Strategies sharing a key share a budget.
+    fn rate_limit_key(&self) -> u64 {
+        0
+    }
+}
+
+/// Hashes a credential so it can key rate-limit windows without being kept around
+pub(crate) fn credential_key(credential: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    credential.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// OAuth 2.0 bearer token (user-context or app-only)
+#[derive(Clone)]
+pub struct BearerAuth {
+    token: String,
+}
+
+impl BearerAuth {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthStrategy for BearerAuth {
+    async fn authorize(&self, request: &mut Request) -> SdkResult<()> {
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", self.token))
+            .map_err(|e| SdkError::AuthConfiguration(e.to_string()))?;
+        value.set_sensitive(true);
+
+        request.headers_mut().insert(AUTHORIZATION, value);
+        Ok(())
+    }
+
+    fn rate_limit_key(&self) -> u64 {
+        credential_key(&self.token)
+    }
+}
+
+impl fmt::Debug for BearerAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BearerAuth")
+            .field("token", &"[redacted]")
+            .finish()
+    }
+}
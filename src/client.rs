@@ -1,28 +1,149 @@
+use crate::auth::{AuthStrategy, BearerAuth, OAuth1Auth, OAuth1Credentials, RefreshingAuth};
 use crate::config::XConfig;
-use crate::error::{SdkError, SdkResult, TwitterApiErrorData};
+use crate::error::{SdkError, SdkResult};
+use crate::middleware::{Middleware, Next};
+use crate::rate_limit::{RateLimitInfo, RateLimiter, endpoint_template};
+use crate::resources::TwitterApiResponse;
 use crate::resources::tweet::TweetHandler;
 use crate::resources::user::UserHandler;
-use reqwest::{Client, Method};
+use crate::retry::RetryPolicy;
+use reqwest::header::{AUTHORIZATION, HeaderMap};
+use reqwest::multipart;
+use reqwest::{Client, Method, Proxy, Request, RequestBuilder, Response, StatusCode, Url};
 use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Hosts that absolute URLs passed to `TwitterClient::send` may point at, besides
+/// the `api_base` host
+pub const X_API_HOSTS: &[&str] = &[
+    "api.x.com",
+    "api.twitter.com",
+    "upload.x.com",
+    "upload.twitter.com",
+];
+
+/// Sent unless the builder sets another `user_agent`
+pub const DEFAULT_USER_AGENT: &str = concat!("rusx/", env!("CARGO_PKG_VERSION"));
+
+/// A decoded response body together with the metadata Twitter sends in headers.
+#[derive(Debug, Clone)]
+pub struct ApiResponse<T> {
+    pub body: T,
+    pub status: u16,
+    pub rate_limit: Option<RateLimitInfo>,
+}
+
+/// The body of a request sent with `TwitterClient::send_with_body`. It is attached
+/// before the request is authorized, so OAuth 1.0a signs form parameters too.
+#[derive(Debug, Clone)]
+pub enum RequestBody {
+    Json(serde_json::Value),
+    /// `application/x-www-form-urlencoded`, e.g. the v1.1 endpoints
+    Form(Vec<(String, String)>),
+    /// `multipart/form-data`, e.g. v1.1 media upload. Not part of the OAuth 1.0a signature.
+    Multipart(Vec<MultipartField>),
+}
+
+impl RequestBody {
+    pub fn form<K: Into<String>, V: Into<String>>(
+        params: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        Self::Form(
+            params
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+
+    fn apply(&self, builder: RequestBuilder) -> RequestBuilder {
+        match self {
+            Self::Json(value) => builder.json(value),
+            Self::Form(params) => builder.form(params),
+            // A multipart body is consumed when sent, so it is rebuilt for every attempt
+            Self::Multipart(fields) => {
+                builder.multipart(fields.iter().fold(multipart::Form::new(), |form, field| {
+                    let mut part = multipart::Part::bytes(field.value.clone());
+                    if let Some(file_name) = &field.file_name {
+                        part = part.file_name(file_name.clone());
+                    }
+                    form.part(field.name.clone(), part)
+                }))
+            }
+        }
+    }
+}
+
+/// One field of a `RequestBody::Multipart`
+#[derive(Debug, Clone)]
+pub struct MultipartField {
+    pub name: String,
+    pub value: Vec<u8>,
+    pub file_name: Option<String>,
+}
+
+impl MultipartField {
+    pub fn text(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into().into_bytes(),
+            file_name: None,
+        }
+    }
+
+    /// Binary content, such as the `media` of a v1.1 upload
+    pub fn file(name: impl Into<String>, file_name: impl Into<String>, value: Vec<u8>) -> Self {
+        Self {
+            name: name.into(),
+            value,
+            file_name: Some(file_name.into()),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct TwitterClient {
     http: Client,
-    bearer_token: String,
+    auth: Arc<dyn AuthStrategy>,
     base_url: String,
+    limiter: Option<Arc<RateLimiter>>,
+    retry: Option<Arc<RetryPolicy>>,
+    middleware: Arc<[Arc<dyn Middleware>]>,
 }
 
 impl TwitterClient {
     pub fn new(bearer_token: String) -> Self {
-        let x_config = XConfig::load();
+        Self::builder().bearer_token(bearer_token).build()
+    }
+
+    /// Starts building a client with an explicit `XConfig` or other non-default settings.
+    pub fn builder() -> TwitterClientBuilder {
+        TwitterClientBuilder::default()
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// A client for other credentials that shares this one's connection pool,
+    /// rate limiter, retry policy and middleware. Cheap enough to call per request.
+    pub fn with_auth(&self, auth: impl AuthStrategy + 'static) -> Self {
+        self.with_shared_auth(Arc::new(auth))
+    }
 
+    pub fn with_shared_auth(&self, auth: Arc<dyn AuthStrategy>) -> Self {
         Self {
-            http: Client::new(),
-            bearer_token,
-            base_url: x_config.api_base,
+            auth,
+            ..self.clone()
         }
     }
 
+    /// Shorthand for `with_auth` with a bearer token.
+    pub fn with_bearer_token(&self, bearer_token: impl Into<String>) -> Self {
+        self.with_auth(BearerAuth::new(bearer_token))
+    }
+
     // Updated: Returns owned handler (no lifetimes needed)
     pub fn tweets(&self) -> TweetHandler {
         TweetHandler::new(self.clone())
@@ -33,34 +154,667 @@ impl TwitterClient {
         UserHandler::new(self.clone())
     }
 
+    /// Used by the resource handlers: decodes the standard v2 envelope and attaches the
+    /// rate-limit state to it.
     pub(crate) async fn request<T: DeserializeOwned>(
         &self,
         method: Method,
         endpoint: &str,
-    ) -> SdkResult<T> {
-        let url = format!("{}{}", self.base_url, endpoint);
+    ) -> SdkResult<TwitterApiResponse<T>> {
+        let response = self.send::<TwitterApiResponse<T>>(method, endpoint).await?;
 
-        let response = self
-            .http
-            .request(method, &url)
-            .bearer_auth(&self.bearer_token)
-            .send()
-            .await?;
+        let mut body = response.body;
+        body.rate_limit = response.rate_limit;
+        Ok(body)
+    }
 
-        let status = response.status();
+    /// Sends an authenticated request to `endpoint` (relative to `api_base`, or an
+    /// absolute URL on the `api_base` host or one of `X_API_HOSTS`) and decodes any JSON body, for endpoints without a typed handler.
+    pub async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+    ) -> SdkResult<ApiResponse<T>> {
+        self.dispatch(method, endpoint, None).await
+    }
 
-        if !status.is_success() {
-            let error_body = response.json::<TwitterApiErrorData>().await.map_err(|_| {
-                SdkError::Unknown("Failed to parse error body from Twitter".to_string())
-            })?;
+    /// Like `send`, with a JSON, form or multipart body.
+    pub async fn send_with_body<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: RequestBody,
+    ) -> SdkResult<ApiResponse<T>> {
+        self.dispatch(method, endpoint, Some(&body)).await
+    }
+
+    // Only the endpoint template is recorded: ids, query strings and headers stay out of the span
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "rusx.request",
+            skip_all,
+            fields(
+                method = %method,
+                endpoint = %self.template(endpoint),
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                rate_limit_remaining = tracing::field::Empty,
+                retries = tracing::field::Empty,
+            )
+        )
+    )]
+    async fn dispatch<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: Option<&RequestBody>,
+    ) -> SdkResult<ApiResponse<T>> {
+        let url = self.resolve_url(endpoint)?;
+        let template = self.template(endpoint);
+
+        let retry = self
+            .retry
+            .as_deref()
+            .filter(|policy| policy.allows(&method));
+
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+
+        let mut attempt = 1;
+        let outcome = loop {
+            let outcome = match self.attempt(&method, &url, body, &template).await {
+                Ok(response) => RawResponse::read(response).await,
+                Err(e) => Err(e),
+            };
+
+            match retry.and_then(|policy| policy.next_delay(&outcome, attempt)) {
+                Some(delay) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        "retrying request"
+                    );
+
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => break outcome,
+            }
+        };
+
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("latency_ms", started.elapsed().as_millis() as u64);
+            span.record("retries", attempt - 1);
+            match &outcome {
+                Ok(response) => {
+                    span.record("status", response.status.as_u16());
+                    if let Some(remaining) = RateLimitInfo::from_headers(&response.headers)
+                        .and_then(|info| info.remaining)
+                    {
+                        span.record("rate_limit_remaining", remaining);
+                    }
+                }
+                // reqwest errors print the full URL, so only their kind and cause are logged
+                Err(SdkError::Http(e)) => tracing::warn!(
+                    error.kind = http_error_kind(e),
+                    error.source = std::error::Error::source(e).map(tracing::field::display),
+                    "request failed"
+                ),
+                Err(e) => tracing::warn!(error = %e, "request failed"),
+            }
+        }
+
+        Self::decode(outcome?, &template)
+    }
+
+    /// The rate-limit key of `endpoint`. Absolute URLs are taken relative to the
+    /// `api_base` path, so `/tweets/1` and `https://api.x.com/2/tweets/1` share a window.
+    fn template(&self, endpoint: &str) -> String {
+        let Ok(url) = Url::parse(endpoint) else {
+            return endpoint_template(endpoint);
+        };
+
+        let base_path = Url::parse(&self.base_url)
+            .map(|base| base.path().trim_end_matches('/').to_string())
+            .unwrap_or_default();
+        let path = url
+            .path()
+            .strip_prefix(base_path.as_str())
+            .filter(|rest| rest.starts_with('/'))
+            .unwrap_or(url.path());
+
+        endpoint_template(path)
+    }
+
+    // Absolute URLs let callers reach other X hosts (e.g. v1.1 media upload), but the
+    // credentials are attached to every request, so foreign hosts are refused
+    fn resolve_url(&self, endpoint: &str) -> SdkResult<String> {
+        if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+            return Ok(format!("{}{}", self.base_url, endpoint));
+        }
+
+        let url = Url::parse(endpoint)
+            .map_err(|e| SdkError::AuthConfiguration(format!("Invalid URL {}: {}", endpoint, e)))?;
+        let base = Url::parse(&self.base_url).ok();
+
+        let same_origin_as_base = base.is_some_and(|base| {
+            base.scheme() == url.scheme()
+                && base.host_str() == url.host_str()
+                && base.port_or_known_default() == url.port_or_known_default()
+        });
+        let known_x_host = url.scheme() == "https"
+            && url
+                .host_str()
+                .is_some_and(|host| X_API_HOSTS.contains(&host));
+
+        if same_origin_as_base || known_x_host {
+            Ok(url.into())
+        } else {
+            Err(SdkError::AuthConfiguration(format!(
+                "Refusing to send credentials to {}: absolute URLs must point at the api_base host or an X API host ({})",
+                url.host_str().unwrap_or_default(),
+                X_API_HOSTS.join(", ")
+            )))
+        }
+    }
+
+    /// One round-trip: limiter, credentials, and a single replay if the strategy
+    /// renewed its credentials after a 401.
+    async fn attempt(
+        &self,
+        method: &Method,
+        url: &str,
+        body: Option<&RequestBody>,
+        template: &str,
+    ) -> SdkResult<Response> {
+        let limit_key = self.auth.rate_limit_key();
+        if let Some(limiter) = &self.limiter {
+            limiter.acquire(template, limit_key).await?;
+        }
+
+        // The body is set before the credentials, since OAuth 1.0a signs form parameters
+        let build = || {
+            let builder = self.http.request(method.clone(), url);
+            match body {
+                Some(body) => body.apply(builder).build(),
+                None => builder.build(),
+            }
+        };
+
+        let mut request = build()?;
+        self.auth.authorize(&mut request).await?;
+
+        let sent_credentials = request.headers().get(AUTHORIZATION).cloned();
+        let mut response = self.execute(request).await?;
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            // Rebuilt rather than cloned, since multipart bodies cannot be cloned. It
+            // carries the rejected credentials until the strategy had a chance to renew them.
+            let mut replay = build()?;
+            if let Some(credentials) = sent_credentials {
+                replay.headers_mut().insert(AUTHORIZATION, credentials);
+            }
+
+            if self.auth.on_unauthorized(&replay).await? {
+                self.auth.authorize(&mut replay).await?;
+                response = self.execute(replay).await?;
+            }
+        }
+
+        if let (Some(limiter), Some(info)) = (
+            &self.limiter,
+            RateLimitInfo::from_headers(response.headers()),
+        ) {
+            limiter.record(template, limit_key, &info);
+        }
+
+        Ok(response)
+    }
+
+    async fn execute(&self, request: Request) -> SdkResult<Response> {
+        Next::new(&self.http, &self.middleware).run(request).await
+    }
+
+    fn decode<T: DeserializeOwned>(
+        response: RawResponse,
+        template: &str,
+    ) -> SdkResult<ApiResponse<T>> {
+        let RawResponse {
+            status,
+            headers,
+            body,
+        } = response;
+        let rate_limit = RateLimitInfo::from_headers(&headers);
+
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            // The monthly usage cap also answers 429, but waiting for the window won't help
+            let error = SdkError::from_response(status.as_u16(), body, rate_limit.clone());
+            if matches!(error, SdkError::UsageCapExceeded { .. }) {
+                return Err(error);
+            }
 
-            return Err(SdkError::Api {
-                status: status.as_u16(),
-                data: error_body,
+            // Otherwise the body is not reliably JSON; the headers carry everything needed
+            let rate_limit = rate_limit.unwrap_or_default();
+            // Same key as the limiter, so errors from either source can be matched up
+            let endpoint = template.to_string();
+
+            // An exhausted 24-hour user cap outlasts the 15-minute window
+            if rate_limit.user_remaining_24h == Some(0) {
+                return Err(SdkError::RateLimited {
+                    reset_at: rate_limit
+                        .user_reset_24h
+                        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+                    limit: rate_limit.user_limit_24h,
+                    remaining: Some(0),
+                    endpoint,
+                });
+            }
+
+            return Err(SdkError::RateLimited {
+                reset_at: rate_limit.reset_at(),
+                limit: rate_limit.limit,
+                remaining: rate_limit.remaining,
+                endpoint,
             });
         }
 
-        let parsed = response.json::<T>().await?;
-        Ok(parsed)
+        if !status.is_success() {
+            return Err(SdkError::from_response(status.as_u16(), body, rate_limit));
+        }
+
+        let parsed = serde_json::from_str::<T>(&body).map_err(|source| SdkError::InvalidBody {
+            status: status.as_u16(),
+            body,
+            source,
+        })?;
+
+        Ok(ApiResponse {
+            body: parsed,
+            status: status.as_u16(),
+            rate_limit,
+        })
+    }
+}
+
+/// A response read in full, so that retries are decided on the classified body
+#[derive(Debug)]
+pub(crate) struct RawResponse {
+    pub(crate) status: StatusCode,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: String,
+}
+
+impl RawResponse {
+    async fn read(response: Response) -> SdkResult<Self> {
+        Ok(Self {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.text().await?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TwitterClientBuilder {
+    config: Option<XConfig>,
+    auth: Option<Arc<dyn AuthStrategy>>,
+    limiter: Option<Arc<RateLimiter>>,
+    retry: Option<Arc<RetryPolicy>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    http: Option<Client>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    user_agent: Option<String>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+}
+
+impl TwitterClientBuilder {
+    /// Overrides the configuration, otherwise `XConfig::load()` is used.
+    pub fn config(mut self, config: XConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn bearer_token(self, bearer_token: impl Into<String>) -> Self {
+        self.auth(BearerAuth::new(bearer_token))
+    }
+
+    /// Signs every request with OAuth 1.0a instead of a bearer token.
+    pub fn oauth1(self, credentials: OAuth1Credentials) -> Self {
+        self.auth(OAuth1Auth::new(credentials))
+    }
+
+    /// Keeps a `TwitterToken` fresh using its refresh token, see `RefreshingAuth`.
+    pub fn refreshing_token(self, auth: RefreshingAuth) -> Self {
+        self.auth(auth)
+    }
+
+    pub fn auth(self, auth: impl AuthStrategy + 'static) -> Self {
+        self.shared_auth(Arc::new(auth))
+    }
+
+    /// Like `auth`, but keeps a handle so the caller can still inspect the strategy
+    /// (e.g. `RefreshingAuth::token`).
+    pub fn shared_auth(mut self, auth: Arc<dyn AuthStrategy>) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Enables client-side rate limiting, see `RateLimiter`.
+    pub fn rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = Some(Arc::new(limiter));
+        self
+    }
+
+    /// Retries transient failures (5xx, 429, connection errors), see `RetryPolicy`.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(Arc::new(policy));
+        self
+    }
+
+    /// Appends a `Middleware`; they run in the order they were added.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Uses a pre-built `reqwest::Client`, e.g. one shared with the rest of the application.
+    /// The timeout, proxy, user agent and pool settings below are then ignored.
+    pub fn http_client(mut self, http: Client) -> Self {
+        self.http = Some(http);
+        self
+    }
+
+    /// Total time allowed for a single attempt, from connecting to reading the body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Routes requests through an HTTP(S) proxy, e.g. `Proxy::all("http://proxy:8080")`.
+    /// Without one, the `HTTP_PROXY`/`HTTPS_PROXY` environment variables are honored.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Defaults to `DEFAULT_USER_AGENT`.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// # Panics
+    ///
+    /// If the HTTP client cannot be created (TLS backend failure, invalid user agent);
+    /// use `try_build` to handle that instead.
+    pub fn build(self) -> TwitterClient {
+        self.try_build()
+            .expect("failed to build the HTTP client for TwitterClient")
+    }
+
+    /// A client with the same timeout, proxy and user agent for OAuth token requests,
+    /// which must not follow redirects with credentials in the body. `None` when a
+    /// pre-built client was given, as its redirect policy cannot be changed.
+    pub(crate) fn oauth_http_client(&self) -> SdkResult<Option<Client>> {
+        if self.http.is_some() {
+            return Ok(None);
+        }
+
+        let http = self
+            .http_builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+        Ok(Some(http))
+    }
+
+    fn http_builder(&self) -> reqwest::ClientBuilder {
+        let mut builder =
+            Client::builder().user_agent(self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT));
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(proxy) = self.proxy.clone() {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+
+        builder
+    }
+
+    pub fn try_build(self) -> SdkResult<TwitterClient> {
+        let http = match &self.http {
+            Some(http) => http.clone(),
+            None => self.http_builder().build()?,
+        };
+
+        let x_config = self.config.unwrap_or_else(XConfig::load);
+
+        Ok(TwitterClient {
+            http,
+            auth: self
+                .auth
+                .unwrap_or_else(|| Arc::new(BearerAuth::new(String::new()))),
+            base_url: x_config.api_base,
+            limiter: self.limiter,
+            retry: self.retry,
+            middleware: self.middleware.into(),
+        })
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn http_error_kind(e: &reqwest::Error) -> &'static str {
+    if e.is_timeout() {
+        "timeout"
+    } else if e.is_connect() {
+        "connect"
+    } else if e.is_redirect() {
+        "redirect"
+    } else if e.is_body() {
+        "body"
+    } else if e.is_decode() {
+        "decode"
+    } else if e.is_builder() {
+        "builder"
+    } else {
+        "request"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::config::ConsumerCredentials;
+
+    fn client() -> TwitterClient {
+        TwitterClient::builder()
+            .config(XConfig::default().with_api_base("http://127.0.0.1:8080/2"))
+            .bearer_token("token")
+            .build()
+    }
+
+    #[test]
+    fn resolves_relative_and_trusted_absolute_urls() {
+        let client = client();
+
+        assert_eq!(
+            client.resolve_url("/users/me").unwrap(),
+            "http://127.0.0.1:8080/2/users/me"
+        );
+        assert!(
+            client
+                .resolve_url("http://127.0.0.1:8080/1.1/x.json")
+                .is_ok()
+        );
+        assert!(
+            client
+                .resolve_url("https://upload.twitter.com/1.1/media/upload.json")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn refuses_to_send_credentials_to_foreign_hosts() {
+        let client = client();
+
+        for url in [
+            "https://evil.example.com/2/users/me",
+            "http://api.twitter.com/2/users/me",
+            "http://127.0.0.1:9999/2/users/me",
+            "https://api.twitter.com.evil.example/2/users/me",
+        ] {
+            assert!(
+                matches!(client.resolve_url(url), Err(SdkError::AuthConfiguration(_))),
+                "{url} should be refused"
+            );
+        }
+    }
+
+    #[test]
+    fn absolute_and_relative_endpoints_share_a_template() {
+        let client = client();
+
+        for endpoint in [
+            "/tweets/1460323737035677698/liking_users?max_results=5",
+            "http://127.0.0.1:8080/2/tweets/1460323737035677698/liking_users",
+            "https://api.x.com/2/tweets/1460323737035677698/liking_users",
+        ] {
+            assert_eq!(client.template(endpoint), "/tweets/:id/liking_users");
+        }
+        assert_eq!(
+            client.template("https://upload.twitter.com/1.1/media/upload.json"),
+            "/1.1/media/upload.json"
+        );
+    }
+
+    /// Keeps the request as it would go on the wire, and never sends it
+    #[derive(Debug, Clone, Default)]
+    struct Capture(Arc<Mutex<Option<Request>>>);
+
+    #[async_trait]
+    impl Middleware for Capture {
+        async fn handle(&self, request: Request, _next: Next<'_>) -> SdkResult<Response> {
+            *self.0.lock().unwrap() = Some(request);
+            Err(SdkError::AuthConfiguration("captured".to_string()))
+        }
+    }
+
+    async fn capture(client: TwitterClientBuilder, body: RequestBody) -> Request {
+        let capture = Capture::default();
+        let client = client
+            .config(XConfig::default().with_api_base("http://127.0.0.1:8080/2"))
+            .middleware(capture.clone())
+            .build();
+
+        let _ = client
+            .send_with_body::<serde_json::Value>(
+                Method::POST,
+                "https://upload.twitter.com/1.1/media/upload.json",
+                body,
+            )
+            .await;
+
+        capture.0.lock().unwrap().take().unwrap()
+    }
+
+    fn oauth1() -> OAuth1Auth {
+        OAuth1Auth::new(OAuth1Credentials {
+            consumer: ConsumerCredentials {
+                api_key: "consumer".to_string(),
+                api_secret: "consumer-secret".to_string(),
+            },
+            access_token: "token".to_string(),
+            access_token_secret: "token-secret".to_string(),
+        })
+    }
+
+    fn header_param<'a>(header: &'a str, name: &str) -> &'a str {
+        header
+            .split(", ")
+            .find_map(|field| field.trim_start_matches("OAuth ").strip_prefix(name))
+            .and_then(|value| value.strip_prefix("=\"")?.strip_suffix('"'))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn signs_form_bodies() {
+        let auth = oauth1();
+        let form = vec![("media_category".to_string(), "tweet_image".to_string())];
+        let request = capture(
+            TwitterClient::builder().auth(auth.clone()),
+            RequestBody::Form(form.clone()),
+        )
+        .await;
+
+        let header = request.headers()[AUTHORIZATION].to_str().unwrap();
+        let nonce = header_param(header, "oauth_nonce");
+        let timestamp = header_param(header, "oauth_timestamp").parse().unwrap();
+        let url = request.url();
+
+        assert_eq!(
+            header,
+            auth.authorization_header(&Method::POST, url, &form, nonce, timestamp)
+        );
+        assert_ne!(
+            header,
+            auth.authorization_header(&Method::POST, url, &[], nonce, timestamp)
+        );
+    }
+
+    #[tokio::test]
+    async fn sends_multipart_bodies() {
+        let body = RequestBody::Multipart(vec![
+            MultipartField::text("media_category", "tweet_image"),
+            MultipartField::file("media", "cat.png", b"not really a png".to_vec()),
+        ]);
+        let request = capture(TwitterClient::builder().auth(oauth1()), body).await;
+
+        let content_type = request.headers()[reqwest::header::CONTENT_TYPE]
+            .to_str()
+            .unwrap();
+        assert!(content_type.starts_with("multipart/form-data; boundary="));
+        assert!(
+            request.headers()[AUTHORIZATION]
+                .to_str()
+                .unwrap()
+                .starts_with("OAuth ")
+        );
     }
 }
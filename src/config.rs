@@ -1,18 +1,98 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+pub const DEFAULT_API_BASE: &str = "https://api.twitter.com/2";
+pub const DEFAULT_OAUTH_URL: &str = "https://twitter.com/i/oauth2/authorize";
+pub const DEFAULT_APP_AUTH_BASE: &str = "https://api.twitter.com";
+
+/// Environment variable overriding `XConfig::api_base`
+pub const API_BASE_ENV: &str = "RUSX_API_BASE";
+/// Environment variable overriding `XConfig::oauth_url`
+pub const OAUTH_URL_ENV: &str = "RUSX_OAUTH_URL";
+/// Environment variable overriding `XConfig::app_auth_base`
+pub const APP_AUTH_BASE_ENV: &str = "RUSX_APP_AUTH_BASE";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct XConfig {
     pub api_base: String,
     pub oauth_url: String,
+    /// Host of the unversioned app-only endpoints (`/oauth2/token`, `/oauth2/invalidate_token`)
+    #[serde(default = "default_app_auth_base")]
+    pub app_auth_base: String,
+}
+
+fn default_app_auth_base() -> String {
+    DEFAULT_APP_AUTH_BASE.to_string()
+}
+
+impl Default for XConfig {
+    fn default() -> Self {
+        Self {
+            api_base: DEFAULT_API_BASE.to_string(),
+            oauth_url: DEFAULT_OAUTH_URL.to_string(),
+            app_auth_base: default_app_auth_base(),
+        }
+    }
 }
 
 impl XConfig {
+    /// Loads the configuration from the environment (`RUSX_API_BASE`, `RUSX_OAUTH_URL`,
+    /// `RUSX_APP_AUTH_BASE`), falling back to the public twitter.com endpoints for anything unset.
     pub fn load() -> Self {
+        Self::from_env()
+    }
+
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
         Self {
-            api_base: "https://api.twitter.com/2".to_string(),
-            oauth_url: "https://twitter.com/i/oauth2/authorize".to_string(),
+            api_base: env_or(API_BASE_ENV, defaults.api_base),
+            oauth_url: env_or(OAUTH_URL_ENV, defaults.oauth_url),
+            app_auth_base: env_or(APP_AUTH_BASE_ENV, defaults.app_auth_base),
         }
     }
+
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_oauth_url(mut self, oauth_url: impl Into<String>) -> Self {
+        self.oauth_url = oauth_url.into();
+        self
+    }
+
+    pub fn with_app_auth_base(mut self, app_auth_base: impl Into<String>) -> Self {
+        self.app_auth_base = app_auth_base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// The OAuth 2.0 token endpoint, derived from `api_base`
+    pub fn token_url(&self) -> String {
+        format!("{}/oauth2/token", self.api_base)
+    }
+
+    /// The OAuth 2.0 token revocation endpoint, derived from `api_base`
+    pub fn revoke_url(&self) -> String {
+        format!("{}/oauth2/revoke", self.api_base)
+    }
+
+    /// The app-only (client credentials) token endpoint
+    pub fn app_token_url(&self) -> String {
+        format!("{}/oauth2/token", self.app_auth_base)
+    }
+
+    pub fn invalidate_token_url(&self) -> String {
+        format!("{}/oauth2/invalidate_token", self.app_auth_base)
+    }
+}
+
+fn env_or(key: &str, default: String) -> String {
+    std::env::var(key)
+        .ok()
+        .map(|v| v.trim().trim_end_matches('/').to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or(default)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,3 +101,21 @@ pub struct OauthConfig {
     pub client_id: String,
     pub client_secret: String,
 }
+
+/// The app's API Key and Secret ("consumer" credentials), used for app-only bearer tokens
+/// and OAuth 1.0a signing.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ConsumerCredentials {
+    pub api_key: String,
+    pub api_secret: String,
+}
+
+// Keep the secret out of logs
+impl fmt::Debug for ConsumerCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConsumerCredentials")
+            .field("api_key", &self.api_key)
+            .field("api_secret", &"[redacted]")
+            .finish()
+    }
+}
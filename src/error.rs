@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 
+use crate::rate_limit::RateLimitInfo;
+
 pub type SdkResult<T> = Result<T, SdkError>;
 
 #[derive(Error, Debug)]
@@ -14,19 +18,256 @@ pub enum SdkError {
     #[error("OAuth configuration or execution error: {0}")]
     AuthConfiguration(String),
 
-    #[error("Twitter API Error {status}: {data:?}")]
+    #[error("OAuth callback state does not match the issued CSRF token")]
+    CsrfMismatch,
+
+    #[error("OAuth session expired before the callback was received")]
+    AuthSessionExpired,
+
+    #[error("Timed out waiting for the OAuth callback")]
+    CallbackTimeout,
+
+    /// The OAuth token endpoint rejected the request, e.g. `invalid_grant` or
+    /// `invalid_request` for a refresh token that was already rotated or revoked.
+    #[error("OAuth token request rejected: {error}{}", description.as_deref().map(|d| format!(" ({})", d)).unwrap_or_default())]
+    OAuth {
+        error: String,
+        description: Option<String>,
+    },
+
+    #[error("Token store error: {0}")]
+    TokenStore(String),
+
+    /// Any other non-success response with a problem body (e.g. 400 validation errors).
+    /// The payloads of this and the variants below are boxed to keep `SdkResult` small.
+    #[error("Twitter API Error {status}: {data}")]
     Api {
         status: u16,
-        data: TwitterApiErrorData,
+        data: Box<TwitterApiErrorData>,
+        rate_limit: Option<Box<RateLimitInfo>>,
+    },
+
+    /// HTTP 401: the token is missing, invalid, expired or revoked.
+    /// The status alone decides this variant (and `Forbidden` / `NotFound`): `data` is
+    /// `None` when the body is not a v2 problem (empty, HTML, v1.1 `errors`), and the
+    /// raw text is kept in `body` either way.
+    #[error("Unauthorized: {}", summary(.data.as_deref(), .body))]
+    Unauthorized {
+        data: Option<Box<TwitterApiErrorData>>,
+        body: String,
+        rate_limit: Option<Box<RateLimitInfo>>,
+    },
+
+    /// HTTP 403: the token lacks a scope, or the user may not access the resource.
+    #[error("Forbidden: {}", summary(.data.as_deref(), .body))]
+    Forbidden {
+        data: Option<Box<TwitterApiErrorData>>,
+        body: String,
+        rate_limit: Option<Box<RateLimitInfo>>,
+    },
+
+    /// HTTP 403 with reason `client-not-enrolled`: the app's project does not have
+    /// access to this endpoint (wrong access tier, or the app is not in a project).
+    #[error("Client not enrolled for this endpoint: {data}")]
+    ClientNotEnrolled {
+        data: Box<TwitterApiErrorData>,
+        rate_limit: Option<Box<RateLimitInfo>>,
+    },
+
+    /// HTTP 404: the endpoint or resource does not exist.
+    #[error("Not found: {}", summary(.data.as_deref(), .body))]
+    NotFound {
+        data: Option<Box<TwitterApiErrorData>>,
+        body: String,
+        rate_limit: Option<Box<RateLimitInfo>>,
+    },
+
+    /// HTTP 429 because the project's monthly usage cap is exhausted. Unlike
+    /// `RateLimited`, waiting for the rate-limit window does not help.
+    #[error("Monthly usage cap exceeded: {data}")]
+    UsageCapExceeded {
+        data: Box<TwitterApiErrorData>,
+        rate_limit: Option<Box<RateLimitInfo>>,
+    },
+
+    /// HTTP 5xx. `body` is kept verbatim since it is often not JSON.
+    #[error("Twitter server error {status}: {body}")]
+    ServerError {
+        status: u16,
+        body: String,
+        rate_limit: Option<Box<RateLimitInfo>>,
+    },
+
+    /// A response body that could not be decoded, kept verbatim in `body`. For error
+    /// responses this only happens on statuses without a dedicated variant.
+    #[error("Unexpected response body (HTTP {status}): {source}")]
+    InvalidBody {
+        status: u16,
+        body: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// HTTP 429. Sleep until `reset_at` before calling `endpoint` again. `endpoint` is
+    /// the template the limit applies to (e.g. `/users/:id`), not the concrete path.
+    #[error("Rate limit exceeded for {endpoint} (resets at {reset_at:?})")]
+    RateLimited {
+        reset_at: Option<SystemTime>,
+        limit: Option<u32>,
+        remaining: Option<u32>,
+        endpoint: String,
     },
 
     #[error("Unknown error occurred: {0}")]
     Unknown(String),
 }
 
+impl SdkError {
+    /// Classifies a non-success response from its status and raw body.
+    pub(crate) fn from_response(
+        status: u16,
+        body: String,
+        rate_limit: Option<RateLimitInfo>,
+    ) -> Self {
+        let rate_limit = rate_limit.map(Box::new);
+
+        if status >= 500 {
+            return Self::ServerError {
+                status,
+                body,
+                rate_limit,
+            };
+        }
+
+        let parsed = serde_json::from_str::<TwitterApiErrorData>(&body).map(Box::new);
+
+        match (status, parsed) {
+            (403, Ok(data)) if data.reason.as_deref() == Some("client-not-enrolled") => {
+                Self::ClientNotEnrolled { data, rate_limit }
+            }
+            (401, parsed) => Self::Unauthorized {
+                data: parsed.ok(),
+                body,
+                rate_limit,
+            },
+            (403, parsed) => Self::Forbidden {
+                data: parsed.ok(),
+                body,
+                rate_limit,
+            },
+            (404, parsed) => Self::NotFound {
+                data: parsed.ok(),
+                body,
+                rate_limit,
+            },
+            (429, Ok(data)) if data.is_usage_capped() => {
+                Self::UsageCapExceeded { data, rate_limit }
+            }
+            (_, Ok(data)) => Self::Api {
+                status,
+                data,
+                rate_limit,
+            },
+            (_, Err(source)) => Self::InvalidBody {
+                status,
+                body,
+                source,
+            },
+        }
+    }
+
+    /// The HTTP status of the failed response, if the error came from one.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Http(e) => e.status().map(|s| s.as_u16()),
+            Self::Api { status, .. }
+            | Self::ServerError { status, .. }
+            | Self::InvalidBody { status, .. } => Some(*status),
+            Self::Unauthorized { .. } => Some(401),
+            Self::Forbidden { .. } | Self::ClientNotEnrolled { .. } => Some(403),
+            Self::NotFound { .. } => Some(404),
+            Self::RateLimited { .. } | Self::UsageCapExceeded { .. } => Some(429),
+            _ => None,
+        }
+    }
+
+    /// The problem body Twitter sent with the failed response, if it was JSON.
+    pub fn api_error(&self) -> Option<&TwitterApiErrorData> {
+        match self {
+            Self::Api { data, .. }
+            | Self::ClientNotEnrolled { data, .. }
+            | Self::UsageCapExceeded { data, .. } => Some(data),
+            Self::Unauthorized { data, .. }
+            | Self::Forbidden { data, .. }
+            | Self::NotFound { data, .. } => data.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The raw response body, for variants that keep it.
+    pub fn body(&self) -> Option<&str> {
+        match self {
+            Self::Unauthorized { body, .. }
+            | Self::Forbidden { body, .. }
+            | Self::NotFound { body, .. }
+            | Self::ServerError { body, .. }
+            | Self::InvalidBody { body, .. } => Some(body),
+            _ => None,
+        }
+    }
+
+    /// The rate-limit state reported alongside the failed response, if any.
+    pub fn rate_limit(&self) -> Option<&RateLimitInfo> {
+        match self {
+            Self::Api { rate_limit, .. }
+            | Self::Unauthorized { rate_limit, .. }
+            | Self::Forbidden { rate_limit, .. }
+            | Self::ClientNotEnrolled { rate_limit, .. }
+            | Self::NotFound { rate_limit, .. }
+            | Self::UsageCapExceeded { rate_limit, .. }
+            | Self::ServerError { rate_limit, .. } => rate_limit.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// True for transient failures where the same request may succeed later:
+    /// network errors, 5xx and rate limiting (after `reset_in`).
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Http(e) => e.is_connect() || e.is_timeout() || e.is_request(),
+            Self::ServerError { .. } | Self::RateLimited { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// For `RateLimited`, how long to wait until the window resets.
+    pub fn reset_in(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited {
+                reset_at: Some(at), ..
+            } => Some(at.duration_since(SystemTime::now()).unwrap_or_default()),
+            _ => None,
+        }
+    }
+}
+
+/// The problem title when there is one, otherwise the start of the raw body
+fn summary(data: Option<&TwitterApiErrorData>, body: &str) -> String {
+    const MAX_CHARS: usize = 200;
+
+    match data {
+        Some(data) => data.to_string(),
+        None if body.trim().is_empty() => "empty response body".to_string(),
+        None if body.chars().count() > MAX_CHARS => {
+            format!("{}...", body.chars().take(MAX_CHARS).collect::<String>())
+        }
+        None => body.to_string(),
+    }
+}
+
 /// Represents the standard error response body from Twitter API v2
 /// Reference: https://developer.twitter.com/en/support/twitter-api/error-troubleshooting
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TwitterApiErrorData {
     pub title: String,
     #[serde(default)]
@@ -36,12 +277,151 @@ pub struct TwitterApiErrorData {
     // Sometimes Twitter returns specific validation errors
     #[serde(default)]
     pub errors: Option<Vec<ValidationError>>,
-     #[serde(default)]
+    #[serde(default)]
     pub status: Option<u16>,
+    /// e.g. `client-not-enrolled` on 403s
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub client_id: Option<String>,
+    #[serde(default)]
+    pub required_enrollment: Option<String>,
+    #[serde(default)]
+    pub registration_url: Option<String>,
+}
+
+/// True for the 429 body X sends once the project's monthly usage cap is exhausted
+pub(crate) fn is_usage_capped(body: &str) -> bool {
+    serde_json::from_str::<TwitterApiErrorData>(body).is_ok_and(|data| data.is_usage_capped())
+}
+
+impl TwitterApiErrorData {
+    fn is_usage_capped(&self) -> bool {
+        self.title == "UsageCapExceeded"
+            || self
+                .error_type
+                .as_deref()
+                .is_some_and(|t| t.ends_with("/usage-capped"))
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+impl fmt::Display for TwitterApiErrorData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.detail {
+            Some(detail) => write!(f, "{} ({})", self.title, detail),
+            None => f.write_str(&self.title),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ValidationError {
     pub message: String,
     pub parameters: Option<serde_json::Value>,
 }
+
+/// An entry of the top-level `errors` array, sent next to `data` when a request
+/// partially succeeds (e.g. some of the requested ids do not exist or are protected).
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ApiProblem {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub detail: Option<String>,
+    /// Problem URI, e.g. `https://api.twitter.com/2/problems/resource-not-found`
+    #[serde(rename = "type", default)]
+    pub problem_type: Option<String>,
+    /// The offending value, usually the id that could not be resolved
+    #[serde(default)]
+    pub value: Option<serde_json::Value>,
+    #[serde(default)]
+    pub parameter: Option<String>,
+    #[serde(default)]
+    pub resource_type: Option<String>,
+    #[serde(default)]
+    pub resource_id: Option<String>,
+    #[serde(default)]
+    pub section: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl ApiProblem {
+    /// The id (or other value) the problem refers to.
+    pub fn resource_id(&self) -> Option<&str> {
+        self.resource_id
+            .as_deref()
+            .or_else(|| self.value.as_ref().and_then(|v| v.as_str()))
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.has_type("resource-not-found")
+    }
+
+    /// The resource exists but the caller may not see it (protected or suspended user)
+    pub fn is_not_authorized(&self) -> bool {
+        self.has_type("not-authorized-for-resource")
+    }
+
+    fn has_type(&self, name: &str) -> bool {
+        self.problem_type
+            .as_deref()
+            .is_some_and(|t| t.rsplit('/').next() == Some(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_decides_the_variant() {
+        let error = SdkError::from_response(401, String::new(), None);
+        assert!(matches!(error, SdkError::Unauthorized { data: None, .. }));
+        assert_eq!(error.to_string(), "Unauthorized: empty response body");
+
+        let html = "<html><body>Forbidden</body></html>".to_string();
+        let error = SdkError::from_response(403, html.clone(), None);
+        assert!(matches!(error, SdkError::Forbidden { data: None, .. }));
+        assert_eq!(error.body(), Some(html.as_str()));
+
+        let v1 = r#"{"errors":[{"code":34,"message":"Sorry, that page does not exist."}]}"#;
+        let error = SdkError::from_response(404, v1.to_string(), None);
+        assert!(matches!(error, SdkError::NotFound { data: None, .. }));
+        assert!(
+            error
+                .to_string()
+                .contains("Sorry, that page does not exist.")
+        );
+        assert_eq!(error.status(), Some(404));
+    }
+
+    #[test]
+    fn keeps_v2_problem_details() {
+        let body =
+            r#"{"title":"Unauthorized","type":"about:blank","status":401,"detail":"Unauthorized"}"#;
+        let error = SdkError::from_response(401, body.to_string(), None);
+        assert_eq!(
+            error.api_error().map(|e| e.title.as_str()),
+            Some("Unauthorized")
+        );
+
+        let body = r#"{"client_id":"123","detail":"When authenticating requests to the Twitter API v2 endpoints, you must use keys and tokens from a Twitter developer App that is attached to a Project.","registration_url":"https://developer.twitter.com/en/docs/projects/overview","title":"Client Forbidden","required_enrollment":"Appropriate Level of API Access","reason":"client-not-enrolled","type":"https://api.twitter.com/2/problems/client-forbidden"}"#;
+        let error = SdkError::from_response(403, body.to_string(), None);
+        assert!(matches!(error, SdkError::ClientNotEnrolled { .. }));
+
+        let body = r#"{"title":"UsageCapExceeded","detail":"Usage cap exceeded: Monthly product cap","type":"https://api.twitter.com/2/problems/usage-capped"}"#;
+        let error = SdkError::from_response(429, body.to_string(), None);
+        assert!(matches!(error, SdkError::UsageCapExceeded { .. }));
+    }
+
+    #[test]
+    fn other_statuses_need_a_problem_body() {
+        let error = SdkError::from_response(400, "oops".to_string(), None);
+        assert!(matches!(error, SdkError::InvalidBody { status: 400, .. }));
+
+        let error = SdkError::from_response(503, "<html></html>".to_string(), None);
+        assert!(matches!(error, SdkError::ServerError { status: 503, .. }));
+        assert!(error.is_retryable());
+    }
+}
@@ -1,16 +1,21 @@
-use crate::auth::TwitterAuth;
-use crate::auth::TwitterToken;
-use crate::client::TwitterClient;
-use crate::config::OauthConfig;
-use crate::error::SdkResult;
+use crate::auth::{RefreshingAuth, Scope, TwitterAuth, TwitterToken};
+use crate::client::{TwitterClient, TwitterClientBuilder};
+use crate::config::{OauthConfig, XConfig};
+use crate::error::{SdkError, SdkResult};
 use crate::resources::search::SearchApi;
 use crate::resources::search::SearchHandler;
 use crate::resources::tweet::{TweetApi, TweetHandler};
 use crate::resources::user::{UserApi, UserHandler};
+use crate::store::TokenStore;
 use async_trait::async_trait;
-use oauth2::PkceCodeVerifier;
+use oauth2::{CsrfToken, PkceCodeVerifier};
+use std::collections::HashMap;
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::OnceCell;
+
+// Each entry is filled once, without holding the map lock across the store read
+type AccountAuths = HashMap<String, Arc<OnceCell<Arc<RefreshingAuth>>>>;
 
 /// The Main Gateway Interface
 #[cfg_attr(feature = "testing", mockall::automock)]
@@ -22,17 +27,37 @@ pub trait TwitterGateway: Debug + Send + Sync {
         verifier: PkceCodeVerifier,
     ) -> SdkResult<TwitterToken>;
 
-    fn generate_auth_url(&self) -> (String, PkceCodeVerifier);
+    async fn refresh_token(&self, refresh_token: String) -> SdkResult<TwitterToken>;
+    async fn revoke_access_token(&self, access_token: String) -> SdkResult<()>;
+    async fn revoke_refresh_token(&self, refresh_token: String) -> SdkResult<()>;
+
+    fn generate_auth_url(&self) -> (String, PkceCodeVerifier, CsrfToken);
+    fn generate_auth_url_with_scopes(
+        &self,
+        scopes: &[Scope],
+    ) -> (String, PkceCodeVerifier, CsrfToken);
     fn users(&self) -> Arc<dyn UserApi>;
     fn tweets(&self) -> Arc<dyn TweetApi>;
     fn search(&self) -> Arc<dyn SearchApi>;
     fn with_token(&self, token: String) -> SdkResult<Arc<dyn TwitterGateway>>;
+
+    /// Resolves a gateway for a connected account from the configured `TokenStore`.
+    /// The token is refreshed automatically and written back to the store before it is
+    /// used; every gateway for the same account shares one refreshing credential.
+    async fn for_account(&self, account_id: &str) -> SdkResult<Arc<dyn TwitterGateway>>;
 }
 
 /// The Concrete Implementation
 #[derive(Clone, Debug)]
 pub struct RusxGateway {
     auth: TwitterAuth,
+    config: XConfig,
+    token_store: Option<Arc<dyn TokenStore>>,
+    // Per-token gateways derive their client from this one to share its connection pool
+    client: TwitterClient,
+    // One refreshing credential per account, shared by every gateway derived from this
+    // one, so concurrent users of an account never refresh (and rotate) it twice
+    accounts: Arc<Mutex<AccountAuths>>,
     // We cache the handlers wrapped in Arc<dyn Trait>
     user_api: Arc<dyn UserApi>,
     tweet_api: Arc<dyn TweetApi>,
@@ -41,17 +66,142 @@ pub struct RusxGateway {
 
 impl RusxGateway {
     pub fn new(oauth_config: OauthConfig, token: Option<String>) -> SdkResult<Self> {
-        let auth = TwitterAuth::new(oauth_config)?;
+        let mut builder = Self::builder(oauth_config);
+        if let Some(token) = token {
+            builder = builder.token(token);
+        }
+        builder.build()
+    }
 
-        let bearer = token.unwrap_or_default();
-        let client = TwitterClient::new(bearer);
+    pub fn builder(oauth_config: OauthConfig) -> RusxGatewayBuilder {
+        RusxGatewayBuilder {
+            oauth_config,
+            config: None,
+            token: None,
+            token_store: None,
+            client: TwitterClient::builder(),
+        }
+    }
 
-        Ok(Self {
+    fn from_parts(
+        auth: TwitterAuth,
+        config: XConfig,
+        token_store: Option<Arc<dyn TokenStore>>,
+        client: TwitterClient,
+        accounts: Arc<Mutex<AccountAuths>>,
+    ) -> Self {
+        Self {
             auth,
+            config,
+            token_store,
+            accounts,
             user_api: Arc::new(UserHandler::new(client.clone())),
             tweet_api: Arc::new(TweetHandler::new(client.clone())),
-            search_api: Arc::new(SearchHandler::new(client)),
-        })
+            search_api: Arc::new(SearchHandler::new(client.clone())),
+            client,
+        }
+    }
+
+    /// Drops the cached credential of an account, so the next `for_account` reads its
+    /// token from the `TokenStore` again (e.g. after the user re-authorized).
+    pub fn forget_account(&self, account_id: &str) {
+        self.lock_accounts().remove(account_id);
+    }
+
+    async fn account_auth(
+        &self,
+        store: &Arc<dyn TokenStore>,
+        account_id: &str,
+    ) -> SdkResult<Arc<RefreshingAuth>> {
+        let cell = self
+            .lock_accounts()
+            .entry(account_id.to_string())
+            .or_default()
+            .clone();
+
+        // Only callers for the same account wait on each other's store read
+        let auth = cell
+            .get_or_try_init(|| async {
+                let token = store.get(account_id).await?.ok_or_else(|| {
+                    SdkError::TokenStore(format!("No token stored for account {}", account_id))
+                })?;
+
+                SdkResult::Ok(Arc::new(
+                    RefreshingAuth::new(self.auth.clone(), token)
+                        .rate_limit_identity(account_id)
+                        .persist_to(store.clone(), account_id),
+                ))
+            })
+            .await?;
+
+        Ok(auth.clone())
+    }
+
+    fn lock_accounts(&self) -> MutexGuard<'_, AccountAuths> {
+        self.accounts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RusxGatewayBuilder {
+    oauth_config: OauthConfig,
+    config: Option<XConfig>,
+    token: Option<String>,
+    token_store: Option<Arc<dyn TokenStore>>,
+    client: TwitterClientBuilder,
+}
+
+impl RusxGatewayBuilder {
+    /// Overrides the configuration used by both the OAuth flow and the API client,
+    /// otherwise `XConfig::load()` is used.
+    pub fn config(mut self, config: XConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Enables `TwitterGateway::for_account`.
+    pub fn token_store(mut self, store: Arc<dyn TokenStore>) -> Self {
+        self.token_store = Some(store);
+        self
+    }
+
+    /// HTTP settings, rate limiter, retry policy and middleware for the API client.
+    /// Shared by every gateway derived through `with_token` / `for_account`; its
+    /// config and credentials are replaced by the gateway's own. The OAuth token,
+    /// refresh and revoke calls use the same timeout, proxy and user agent, but never
+    /// follow redirects.
+    pub fn client(mut self, client: TwitterClientBuilder) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn build(self) -> SdkResult<RusxGateway> {
+        let config = self.config.unwrap_or_else(XConfig::load);
+
+        // OAuth calls (including refreshes in `for_account`) use the same HTTP settings
+        let mut auth = TwitterAuth::with_config(self.oauth_config, config.clone())?;
+        if let Some(http) = self.client.oauth_http_client()? {
+            auth = auth.with_http_client(http);
+        }
+
+        let client = self
+            .client
+            .config(config.clone())
+            .bearer_token(self.token.unwrap_or_default())
+            .try_build()?;
+
+        Ok(RusxGateway::from_parts(
+            auth,
+            config,
+            self.token_store,
+            client,
+            Default::default(),
+        ))
     }
 }
 
@@ -65,10 +215,29 @@ impl TwitterGateway for RusxGateway {
         self.auth.exchange_code(code, verifier).await
     }
 
-    fn generate_auth_url(&self) -> (String, PkceCodeVerifier) {
+    async fn refresh_token(&self, refresh_token: String) -> SdkResult<TwitterToken> {
+        self.auth.refresh_token(refresh_token).await
+    }
+
+    async fn revoke_access_token(&self, access_token: String) -> SdkResult<()> {
+        self.auth.revoke_access_token(access_token).await
+    }
+
+    async fn revoke_refresh_token(&self, refresh_token: String) -> SdkResult<()> {
+        self.auth.revoke_refresh_token(refresh_token).await
+    }
+
+    fn generate_auth_url(&self) -> (String, PkceCodeVerifier, CsrfToken) {
         self.auth.generate_auth_url()
     }
 
+    fn generate_auth_url_with_scopes(
+        &self,
+        scopes: &[Scope],
+    ) -> (String, PkceCodeVerifier, CsrfToken) {
+        self.auth.generate_auth_url_with_scopes(scopes)
+    }
+
     fn users(&self) -> Arc<dyn UserApi> {
         self.user_api.clone()
     }
@@ -82,14 +251,34 @@ impl TwitterGateway for RusxGateway {
     }
 
     fn with_token(&self, token: String) -> SdkResult<Arc<dyn TwitterGateway>> {
-        let client = TwitterClient::new(token);
+        let client = self.client.with_bearer_token(token);
 
-        let new_gateway = RusxGateway {
-            auth: self.auth.clone(),
-            user_api: Arc::new(UserHandler::new(client.clone())),
-            tweet_api: Arc::new(TweetHandler::new(client.clone())),
-            search_api: Arc::new(SearchHandler::new(client)),
-        };
+        let new_gateway = RusxGateway::from_parts(
+            self.auth.clone(),
+            self.config.clone(),
+            self.token_store.clone(),
+            client,
+            self.accounts.clone(),
+        );
+
+        Ok(Arc::new(new_gateway))
+    }
+
+    async fn for_account(&self, account_id: &str) -> SdkResult<Arc<dyn TwitterGateway>> {
+        let store = self.token_store.clone().ok_or_else(|| {
+            SdkError::TokenStore("No TokenStore configured on this gateway".to_string())
+        })?;
+
+        let auth = self.account_auth(&store, account_id).await?;
+        let client = self.client.with_shared_auth(auth);
+
+        let new_gateway = RusxGateway::from_parts(
+            self.auth.clone(),
+            self.config.clone(),
+            Some(store),
+            client,
+            self.accounts.clone(),
+        );
 
         Ok(Arc::new(new_gateway))
     }
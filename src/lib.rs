@@ -3,13 +3,21 @@ pub mod client;
 pub mod config;
 pub mod error;
 pub mod gateway;
+pub mod middleware;
+pub mod rate_limit;
 pub mod resources;
+pub mod retry;
+pub mod store;
 
 // Re-export the Client for easy access
-pub use auth::TwitterAuth;
-pub use client::TwitterClient;
+pub use auth::{AuthSession, AuthStrategy, OAuth1Credentials, RefreshingAuth, Scope, TwitterAuth};
+pub use client::{ApiResponse, MultipartField, RequestBody, TwitterClient};
 pub use gateway::{RusxGateway, TwitterGateway};
-pub use oauth2::PkceCodeVerifier;
+pub use middleware::{Middleware, Next};
+pub use oauth2::{CsrfToken, PkceCodeVerifier};
+pub use rate_limit::{RateLimitInfo, RateLimitPolicy, RateLimiter};
+pub use retry::RetryPolicy;
+pub use store::{FileTokenStore, InMemoryTokenStore, TokenStore};
 
 #[cfg(feature = "testing")]
 pub use gateway::MockTwitterGateway;
@@ -17,3 +25,5 @@ pub use gateway::MockTwitterGateway;
 pub use resources::tweet::MockTweetApi;
 #[cfg(feature = "testing")]
 pub use resources::user::MockUserApi;
+#[cfg(feature = "testing")]
+pub use store::MockTokenStore;
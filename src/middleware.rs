@@ -0,0 +1,46 @@
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use reqwest::{Client, Request, Response};
+
+use crate::error::SdkResult;
+
+/// Hook around every HTTP round-trip made by `TwitterClient`, for cross-cutting
+/// concerns such as extra headers, logging, metrics or fault injection in tests.
+///
+/// Middleware runs in the order it was added to the builder, once per attempt
+/// (including retries and the replay after a token refresh), and sees the request
+/// after credentials were added. Call `next.run(request)` to continue the chain, or
+/// return a response or error without calling it to short-circuit.
+#[async_trait]
+pub trait Middleware: fmt::Debug + Send + Sync {
+    async fn handle(&self, request: Request, next: Next<'_>) -> SdkResult<Response>;
+}
+
+/// The rest of the chain, ending with the actual HTTP call.
+pub struct Next<'a> {
+    http: &'a Client,
+    middleware: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(http: &'a Client, middleware: &'a [Arc<dyn Middleware>]) -> Self {
+        Self { http, middleware }
+    }
+
+    pub async fn run(self, request: Request) -> SdkResult<Response> {
+        match self.middleware.split_first() {
+            Some((current, rest)) => current.handle(request, Next::new(self.http, rest)).await,
+            None => Ok(self.http.execute(request).await?),
+        }
+    }
+}
+
+impl fmt::Debug for Next<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Next")
+            .field("remaining", &self.middleware.len())
+            .finish()
+    }
+}
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{SdkError, SdkResult};
+
+pub const LIMIT_HEADER: &str = "x-rate-limit-limit";
+pub const REMAINING_HEADER: &str = "x-rate-limit-remaining";
+pub const RESET_HEADER: &str = "x-rate-limit-reset";
+pub const USER_24H_LIMIT_HEADER: &str = "x-user-limit-24hour-limit";
+pub const USER_24H_REMAINING_HEADER: &str = "x-user-limit-24hour-remaining";
+pub const USER_24H_RESET_HEADER: &str = "x-user-limit-24hour-reset";
+
+/// Expired windows are dropped once every this many limiter calls
+const SWEEP_INTERVAL: usize = 256;
+
+/// Rate-limit state reported by Twitter on every response.
+/// Reference: https://docs.x.com/x-api/fundamentals/rate-limits
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitInfo {
+    /// Requests allowed in the current 15-minute window
+    pub limit: Option<u32>,
+    pub remaining: Option<u32>,
+    /// Unix timestamp (seconds) at which the window resets
+    pub reset: Option<u64>,
+    /// Per-user 24-hour cap, only sent by some endpoints (e.g. posting)
+    pub user_limit_24h: Option<u32>,
+    pub user_remaining_24h: Option<u32>,
+    pub user_reset_24h: Option<u64>,
+}
+
+impl RateLimitInfo {
+    /// Returns `None` if the response carried no rate-limit headers at all.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let info = Self {
+            limit: header_value(headers, LIMIT_HEADER),
+            remaining: header_value(headers, REMAINING_HEADER),
+            reset: header_value(headers, RESET_HEADER),
+            user_limit_24h: header_value(headers, USER_24H_LIMIT_HEADER),
+            user_remaining_24h: header_value(headers, USER_24H_REMAINING_HEADER),
+            user_reset_24h: header_value(headers, USER_24H_RESET_HEADER),
+        };
+
+        (info != Self::default()).then_some(info)
+    }
+
+    pub fn reset_at(&self) -> Option<SystemTime> {
+        self.reset
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    /// Time left until the window resets (zero if it already has).
+    pub fn reset_in(&self) -> Option<Duration> {
+        self.reset_at()
+            .map(|at| at.duration_since(SystemTime::now()).unwrap_or_default())
+    }
+
+    /// Time left until the per-user 24-hour cap resets, if the endpoint sends one.
+    pub fn user_reset_24h_in(&self) -> Option<Duration> {
+        self.user_reset_24h.map(|secs| {
+            (UNIX_EPOCH + Duration::from_secs(secs))
+                .duration_since(SystemTime::now())
+                .unwrap_or_default()
+        })
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == Some(0) || self.user_remaining_24h == Some(0)
+    }
+}
+
+fn header_value<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+/// What `RateLimiter` does when a window is exhausted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitPolicy {
+    /// Sleep until the window resets, then send the request
+    #[default]
+    Wait,
+    /// Return `SdkError::RateLimited` without calling the API
+    FailFast,
+}
+
+/// Opt-in client-side limiter that tracks X's 15-minute windows per endpoint template
+/// (e.g. `/tweets/search/recent`, `/users/:id`) and per credential, learning the
+/// limits from the rate-limit headers of each response.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    policy: RateLimitPolicy,
+    /// Known limits used before the first response for a window has been seen
+    seeded: HashMap<String, (u32, Duration)>,
+    windows: Mutex<HashMap<(String, u64), Window>>,
+    calls: AtomicUsize,
+}
+
+#[derive(Debug, Clone)]
+struct Window {
+    limit: Option<u32>,
+    remaining: u32,
+    reset_at: SystemTime,
+}
+
+impl RateLimiter {
+    pub fn new(policy: RateLimitPolicy) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    /// Declares a known limit up front, e.g. `("/tweets/search/recent", 450, 15 min)`.
+    pub fn with_limit(mut self, endpoint_template: &str, limit: u32, window: Duration) -> Self {
+        self.seeded
+            .insert(endpoint_template.to_string(), (limit, window));
+        self
+    }
+
+    pub fn policy(&self) -> RateLimitPolicy {
+        self.policy
+    }
+
+    /// Reserves one request on the window, waiting or failing per the policy.
+    pub(crate) async fn acquire(&self, endpoint: &str, key: u64) -> SdkResult<()> {
+        let template = endpoint_template(endpoint);
+        let template = template.as_str();
+
+        loop {
+            let blocked_until = {
+                let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+                let now = SystemTime::now();
+                self.sweep(&mut windows, now);
+                let slot = (template.to_string(), key);
+
+                if windows.get(&slot).is_some_and(|w| now >= w.reset_at) {
+                    windows.remove(&slot);
+                }
+
+                if !windows.contains_key(&slot)
+                    && let Some((limit, window)) = self.seeded.get(template)
+                {
+                    windows.insert(
+                        slot.clone(),
+                        Window {
+                            limit: Some(*limit),
+                            remaining: *limit,
+                            reset_at: now + *window,
+                        },
+                    );
+                }
+
+                match windows.get_mut(&slot) {
+                    // Unknown window: let the request through and learn from its headers
+                    None => return Ok(()),
+                    Some(window) if window.remaining > 0 => {
+                        window.remaining -= 1;
+                        return Ok(());
+                    }
+                    Some(window) => window.clone(),
+                }
+            };
+
+            match self.policy {
+                RateLimitPolicy::FailFast => {
+                    return Err(SdkError::RateLimited {
+                        reset_at: Some(blocked_until.reset_at),
+                        limit: blocked_until.limit,
+                        remaining: Some(0),
+                        endpoint: template.to_string(),
+                    });
+                }
+                RateLimitPolicy::Wait => {
+                    let wait = blocked_until
+                        .reset_at
+                        .duration_since(SystemTime::now())
+                        .unwrap_or_default();
+                    // Reset timestamps have one-second granularity
+                    tokio::time::sleep(wait + Duration::from_secs(1)).await;
+                }
+            }
+        }
+    }
+
+    /// Updates the window from the headers of a response.
+    pub(crate) fn record(&self, endpoint: &str, key: u64, info: &RateLimitInfo) {
+        // A spent 24-hour user cap blocks the endpoint beyond the 15-minute window
+        let (limit, remaining, reset_at) = match (info.user_remaining_24h, info.user_reset_24h) {
+            (Some(0), Some(reset)) => (
+                info.user_limit_24h,
+                0,
+                UNIX_EPOCH + Duration::from_secs(reset),
+            ),
+            _ => match (info.remaining, info.reset_at()) {
+                (Some(remaining), Some(reset_at)) => (info.limit, remaining, reset_at),
+                _ => return,
+            },
+        };
+
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        self.sweep(&mut windows, SystemTime::now());
+        windows.insert(
+            (endpoint_template(endpoint), key),
+            Window {
+                limit,
+                remaining,
+                reset_at,
+            },
+        );
+    }
+
+    // Every endpoint and credential ever seen gets a window, so without this the map
+    // would grow for as long as the limiter lives (e.g. one entry per connected account)
+    fn sweep(&self, windows: &mut HashMap<(String, u64), Window>, now: SystemTime) {
+        if self.calls.fetch_add(1, Ordering::Relaxed) % SWEEP_INTERVAL == SWEEP_INTERVAL - 1 {
+            windows.retain(|_, window| window.reset_at > now);
+        }
+    }
+}
+
+/// Collapses a concrete endpoint into the template X applies limits to:
+/// the query string is dropped and ids / usernames become placeholders.
+pub fn endpoint_template(endpoint: &str) -> String {
+    let path = endpoint.split(['?', '#']).next().unwrap_or_default();
+    // Absolute URLs: keep only the path
+    let path = match path.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |i| &rest[i..]),
+        None => path,
+    };
+
+    let mut previous = "";
+    path.split('/')
+        .enumerate()
+        .map(|(i, segment)| {
+            // The first segment is a resource or, in absolute URLs, the API version ("/2")
+            let mapped = if i <= 1 {
+                segment
+            } else if previous == "username" {
+                ":username"
+            } else if !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()) {
+                ":id"
+            } else {
+                segment
+            };
+            previous = segment;
+            mapped
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn templates_ids_usernames_and_queries() {
+        assert_eq!(
+            endpoint_template("/users/2244994945?user.fields=id"),
+            "/users/:id"
+        );
+        assert_eq!(
+            endpoint_template("/users/by/username/XDevelopers"),
+            "/users/by/username/:username"
+        );
+        assert_eq!(endpoint_template("/users/:id"), "/users/:id");
+    }
+
+    #[tokio::test]
+    async fn fail_fast_reports_the_template() {
+        let limiter = RateLimiter::new(RateLimitPolicy::FailFast);
+        let exhausted = RateLimitInfo {
+            limit: Some(900),
+            remaining: Some(0),
+            reset: Some(unix_in(60)),
+            ..Default::default()
+        };
+        limiter.record("/users/12?expansions=pinned_tweet_id", 1, &exhausted);
+
+        match limiter.acquire("/users/34", 1).await.err() {
+            Some(SdkError::RateLimited {
+                endpoint, limit, ..
+            }) => {
+                assert_eq!(endpoint, "/users/:id");
+                assert_eq!(limit, Some(900));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // Other credentials have their own window
+        assert!(limiter.acquire("/users/34", 2).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn spent_daily_cap_blocks_past_the_window() {
+        let limiter = RateLimiter::new(RateLimitPolicy::FailFast);
+        let daily_reset = unix_in(20 * 3600);
+        let daily_cap = RateLimitInfo {
+            limit: Some(200),
+            remaining: Some(150),
+            reset: Some(unix_in(60)),
+            user_limit_24h: Some(17),
+            user_remaining_24h: Some(0),
+            user_reset_24h: Some(daily_reset),
+        };
+        limiter.record("/tweets", 1, &daily_cap);
+
+        match limiter.acquire("/tweets", 1).await.err() {
+            Some(SdkError::RateLimited {
+                limit, reset_at, ..
+            }) => {
+                assert_eq!(limit, Some(17));
+                assert_eq!(
+                    reset_at,
+                    Some(UNIX_EPOCH + Duration::from_secs(daily_reset))
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn drops_expired_windows() {
+        let limiter = RateLimiter::default();
+        let expired = RateLimitInfo {
+            remaining: Some(10),
+            reset: Some(1),
+            ..Default::default()
+        };
+        for key in 0..SWEEP_INTERVAL as u64 {
+            limiter.record("/users/me", key, &expired);
+        }
+
+        assert!(limiter.windows.lock().unwrap().len() < SWEEP_INTERVAL);
+    }
+
+    fn unix_in(secs: u64) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + secs
+    }
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+// Entities shared by Tweets and User profiles. `start` and `end` are offsets into
+// the text, counted in Unicode code points (not bytes).
+// Reference: https://docs.x.com/x-api/fundamentals/data-dictionary
+
+/// A link, with the t.co `url` expanded
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UrlEntity {
+    pub start: u32,
+    pub end: u32,
+    /// The t.co URL as it appears in the text
+    pub url: String,
+    #[serde(default)]
+    pub expanded_url: Option<String>,
+    #[serde(default)]
+    pub display_url: Option<String>,
+    /// The final destination after redirects (enriched URLs)
+    #[serde(default)]
+    pub unwound_url: Option<String>,
+    /// HTTP status of the destination
+    #[serde(default)]
+    pub status: Option<u16>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub images: Option<Vec<UrlImage>>,
+    /// Set when the link points at attached media
+    #[serde(default)]
+    pub media_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UrlImage {
+    pub url: String,
+    #[serde(default)]
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub height: Option<u32>,
+}
+
+/// A `#hashtag` or `$cashtag`, without the leading symbol
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TagEntity {
+    pub start: u32,
+    pub end: u32,
+    pub tag: String,
+}
+
+/// An `@mention`, without the leading `@`
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MentionEntity {
+    pub start: u32,
+    pub end: u32,
+    pub username: String,
+    /// Only set on Tweets
+    #[serde(default)]
+    pub id: Option<String>,
+}
+
+/// A named entity (person, place, product, ...) detected in the text
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AnnotationEntity {
+    pub start: u32,
+    pub end: u32,
+    /// Confidence between 0 and 1
+    #[serde(default)]
+    pub probability: Option<f64>,
+    /// e.g. "Person", "Place", "Product", "Organization", "Other"
+    #[serde(rename = "type", default)]
+    pub annotation_type: Option<String>,
+    #[serde(default)]
+    pub normalized_text: Option<String>,
+}
+
+/// The entities parsed from a Tweet's (or note Tweet's) text
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TweetEntities {
+    #[serde(default)]
+    pub annotations: Option<Vec<AnnotationEntity>>,
+    #[serde(default)]
+    pub cashtags: Option<Vec<TagEntity>>,
+    #[serde(default)]
+    pub hashtags: Option<Vec<TagEntity>>,
+    #[serde(default)]
+    pub mentions: Option<Vec<MentionEntity>>,
+    #[serde(default)]
+    pub urls: Option<Vec<UrlEntity>>,
+}
+
+/// The entities of a user profile, see `User::entities`
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct UserEntities {
+    /// The expanded profile `url`
+    #[serde(default)]
+    pub url: Option<ProfileEntities>,
+    /// Links, hashtags, mentions and cashtags in the bio
+    #[serde(default)]
+    pub description: Option<ProfileEntities>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ProfileEntities {
+    #[serde(default)]
+    pub urls: Option<Vec<UrlEntity>>,
+    #[serde(default)]
+    pub hashtags: Option<Vec<TagEntity>>,
+    #[serde(default)]
+    pub mentions: Option<Vec<MentionEntity>>,
+    #[serde(default)]
+    pub cashtags: Option<Vec<TagEntity>>,
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use crate::resources::media::Media;
+use crate::resources::place::Place;
+use crate::resources::poll::Poll;
+use crate::resources::tweet::{ReferencedTweetKind, Tweet};
+use crate::resources::user::User;
+use crate::resources::{Includes, TwitterApiResponse};
+
+/// A Tweet joined with the objects it references from `includes`. Anything that
+/// was not expanded (or is missing from `includes`) is left empty.
+#[derive(Debug, Clone)]
+pub struct HydratedTweet<'a> {
+    pub tweet: &'a Tweet,
+    pub author: Option<&'a User>,
+    pub referenced_tweets: Vec<HydratedReference<'a>>,
+    pub media: Vec<&'a Media>,
+    pub poll: Option<&'a Poll>,
+    pub place: Option<&'a Place>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HydratedReference<'a> {
+    pub kind: ReferencedTweetKind,
+    pub id: &'a str,
+    /// Needs `TweetExpansion::ReferencedTweetsId`
+    pub tweet: Option<&'a Tweet>,
+    /// Needs `TweetExpansion::ReferencedTweetsIdAuthorId`
+    pub author: Option<&'a User>,
+}
+
+impl<'a> HydratedTweet<'a> {
+    /// The Tweet this one quotes, if it was expanded.
+    pub fn quoted(&self) -> Option<&HydratedReference<'a>> {
+        self.reference(ReferencedTweetKind::Quoted)
+    }
+
+    /// The Tweet this one replies to, if it was expanded.
+    pub fn replied_to(&self) -> Option<&HydratedReference<'a>> {
+        self.reference(ReferencedTweetKind::RepliedTo)
+    }
+
+    /// The original Tweet of a retweet, if it was expanded.
+    pub fn retweeted(&self) -> Option<&HydratedReference<'a>> {
+        self.reference(ReferencedTweetKind::Retweeted)
+    }
+
+    fn reference(&self, kind: ReferencedTweetKind) -> Option<&HydratedReference<'a>> {
+        self.referenced_tweets.iter().find(|r| r.kind == kind)
+    }
+}
+
+/// Lookup tables over `Includes`, keyed by id (media by `media_key`).
+/// Build it once per response and hydrate any number of Tweets against it.
+#[derive(Debug, Clone, Default)]
+pub struct IncludesIndex<'a> {
+    pub users: HashMap<&'a str, &'a User>,
+    pub tweets: HashMap<&'a str, &'a Tweet>,
+    pub media: HashMap<&'a str, &'a Media>,
+    pub places: HashMap<&'a str, &'a Place>,
+    pub polls: HashMap<&'a str, &'a Poll>,
+}
+
+impl<'a> IncludesIndex<'a> {
+    pub fn new(includes: Option<&'a Includes>) -> Self {
+        let Some(includes) = includes else {
+            return Self::default();
+        };
+
+        Self {
+            users: index(&includes.users, |u| &u.id),
+            tweets: index(&includes.tweets, |t| &t.id),
+            media: index(&includes.media, |m| &m.media_key),
+            places: index(&includes.places, |p| &p.id),
+            polls: index(&includes.polls, |p| &p.id),
+        }
+    }
+
+    pub fn hydrate(&self, tweet: &'a Tweet) -> HydratedTweet<'a> {
+        let attachments = tweet.attachments.as_ref();
+
+        HydratedTweet {
+            tweet,
+            author: self.user(tweet.author_id.as_deref()),
+            referenced_tweets: tweet
+                .referenced_tweets
+                .iter()
+                .flatten()
+                .map(|reference| {
+                    let referenced = self.tweets.get(reference.id.as_str()).copied();
+                    HydratedReference {
+                        kind: reference.kind,
+                        id: &reference.id,
+                        tweet: referenced,
+                        author: self.user(referenced.and_then(|t| t.author_id.as_deref())),
+                    }
+                })
+                .collect(),
+            media: attachments
+                .and_then(|a| a.media_keys.as_ref())
+                .into_iter()
+                .flatten()
+                .filter_map(|key| self.media.get(key.as_str()).copied())
+                .collect(),
+            poll: attachments
+                .and_then(|a| a.poll_ids.as_ref())
+                .and_then(|ids| ids.first())
+                .and_then(|id| self.polls.get(id.as_str()).copied()),
+            place: tweet
+                .geo
+                .as_ref()
+                .and_then(|g| g.place_id.as_deref())
+                .and_then(|id| self.places.get(id).copied()),
+        }
+    }
+
+    fn user(&self, id: Option<&str>) -> Option<&'a User> {
+        id.and_then(|id| self.users.get(id).copied())
+    }
+}
+
+fn index<'a, T>(
+    items: &'a Option<Vec<T>>,
+    key: impl Fn(&'a T) -> &'a String,
+) -> HashMap<&'a str, &'a T> {
+    items
+        .iter()
+        .flatten()
+        .map(|item| (key(item).as_str(), item))
+        .collect()
+}
+
+impl TwitterApiResponse<Vec<Tweet>> {
+    /// Joins every Tweet in `data` with its author, referenced Tweets, media, poll
+    /// and place from `includes`, e.g. for search results and timelines.
+    pub fn hydrated(&self) -> Vec<HydratedTweet<'_>> {
+        let index = IncludesIndex::new(self.includes.as_ref());
+        self.data
+            .iter()
+            .flatten()
+            .map(|tweet| index.hydrate(tweet))
+            .collect()
+    }
+}
+
+impl TwitterApiResponse<Tweet> {
+    /// Single-Tweet lookup counterpart of `TwitterApiResponse<Vec<Tweet>>::hydrated`.
+    pub fn hydrated(&self) -> Option<HydratedTweet<'_>> {
+        let index = IncludesIndex::new(self.includes.as_ref());
+        self.data.as_ref().map(|tweet| index.hydrate(tweet))
+    }
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+/// An attached photo, video or GIF, returned in `includes.media` for the
+/// `attachments.media_keys` expansion.
+/// Reference: https://docs.x.com/x-api/fundamentals/data-dictionary#media
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Media {
+    pub media_key: String,
+    #[serde(rename = "type")]
+    pub media_type: MediaType,
+    /// Direct URL of a photo
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    #[serde(default)]
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub width: Option<u32>,
+    /// Static preview of a video or GIF
+    #[serde(default)]
+    pub preview_image_url: Option<String>,
+    #[serde(default)]
+    pub alt_text: Option<String>,
+    /// The available encodings of a video or GIF
+    #[serde(default)]
+    pub variants: Option<Vec<MediaVariant>>,
+    #[serde(default)]
+    pub public_metrics: Option<MediaPublicMetrics>,
+    /// Only available to the owner of the Tweet (user context)
+    #[serde(default)]
+    pub non_public_metrics: Option<MediaPlaybackMetrics>,
+    #[serde(default)]
+    pub organic_metrics: Option<MediaPlaybackMetrics>,
+    #[serde(default)]
+    pub promoted_metrics: Option<MediaPlaybackMetrics>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaType {
+    Photo,
+    Video,
+    AnimatedGif,
+    /// A media type this SDK does not know about yet
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MediaVariant {
+    #[serde(default)]
+    pub bit_rate: Option<u64>,
+    pub content_type: String,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct MediaPublicMetrics {
+    #[serde(default)]
+    pub view_count: Option<u64>,
+}
+
+/// Video playback quartiles (and views, for organic/promoted metrics)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct MediaPlaybackMetrics {
+    #[serde(default)]
+    pub playback_0_count: Option<u64>,
+    #[serde(default)]
+    pub playback_25_count: Option<u64>,
+    #[serde(default)]
+    pub playback_50_count: Option<u64>,
+    #[serde(default)]
+    pub playback_75_count: Option<u64>,
+    #[serde(default)]
+    pub playback_100_count: Option<u64>,
+    #[serde(default)]
+    pub view_count: Option<u64>,
+}
@@ -1,8 +1,19 @@
 use serde::{Deserialize, Serialize};
 
-use crate::resources::{search::SearchMeta, user::User};
+use crate::error::ApiProblem;
+use crate::rate_limit::RateLimitInfo;
+use crate::resources::{
+    media::Media, place::Place, poll::Poll, search::SearchMeta, topic::Topic, tweet::Tweet,
+    user::User,
+};
 
+pub mod entities;
+pub mod hydrate;
+pub mod media;
+pub mod place;
+pub mod poll;
 pub mod search;
+pub mod topic;
 pub mod tweet;
 pub mod user;
 
@@ -222,9 +233,36 @@ impl AsQueryStr for TweetExpansion {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Content withheld in some countries, on Tweets and Users
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Withheld {
+    /// Withheld because of a DMCA (copyright) complaint
+    #[serde(default)]
+    pub copyright: Option<bool>,
+    /// ISO 3166-1 alpha-2 codes, or `XX` (all countries) / `XY` (Germany, DMCA)
+    #[serde(default)]
+    pub country_codes: Vec<String>,
+    /// "tweet" or "user"
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+/// Objects referenced by `data` and pulled in through `expansions`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Includes {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub users: Option<Vec<User>>,
+    /// Referenced (quoted, replied-to, retweeted) and pinned Tweets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tweets: Option<Vec<Tweet>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media: Option<Vec<Media>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub places: Option<Vec<Place>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub polls: Option<Vec<Poll>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topics: Option<Vec<Topic>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -234,4 +272,39 @@ pub struct TwitterApiResponse<T> {
     pub includes: Option<Includes>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub meta: Option<SearchMeta>,
+    /// Problems for the parts of a 200 response that could not be returned,
+    /// e.g. ids that were not found or belong to protected accounts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ApiProblem>,
+    /// Parsed from the response headers, not part of the JSON body
+    #[serde(skip)]
+    pub rate_limit: Option<RateLimitInfo>,
+}
+
+impl<T> Default for TwitterApiResponse<T> {
+    fn default() -> Self {
+        Self {
+            data: None,
+            includes: None,
+            meta: None,
+            errors: Vec::new(),
+            rate_limit: None,
+        }
+    }
+}
+
+impl<T> TwitterApiResponse<T> {
+    /// True when the request only partially succeeded, see `errors`.
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Ids from `errors` that do not exist (deleted tweets, unknown users, ...).
+    pub fn not_found_ids(&self) -> Vec<&str> {
+        self.errors
+            .iter()
+            .filter(|problem| problem.is_not_found())
+            .filter_map(|problem| problem.resource_id())
+            .collect()
+    }
 }
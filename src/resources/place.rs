@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// A tagged location, returned in `includes.places` for the `geo.place_id` expansion.
+/// Reference: https://docs.x.com/x-api/fundamentals/data-dictionary#place
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Place {
+    pub id: String,
+    /// e.g. "Manhattan, NY"
+    pub full_name: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub country: Option<String>,
+    /// ISO 3166-1 alpha-2
+    #[serde(default)]
+    pub country_code: Option<String>,
+    #[serde(default)]
+    pub place_type: Option<PlaceType>,
+    /// Ids of the places this one lies within
+    #[serde(default)]
+    pub contained_within: Option<Vec<String>>,
+    #[serde(default)]
+    pub geo: Option<PlaceGeo>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaceType {
+    Poi,
+    Neighborhood,
+    City,
+    Admin,
+    Country,
+    #[serde(other)]
+    Unknown,
+}
+
+/// GeoJSON feature describing the place
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PlaceGeo {
+    /// Usually "Feature"
+    #[serde(rename = "type", default)]
+    pub geo_type: Option<String>,
+    /// `[west, south, east, north]` in degrees
+    #[serde(default)]
+    pub bbox: Option<Vec<f64>>,
+    #[serde(default)]
+    pub geometry: Option<serde_json::Value>,
+    #[serde(default)]
+    pub properties: Option<serde_json::Value>,
+}
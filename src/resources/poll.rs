@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// A poll attached to a Tweet, returned in `includes.polls` for the
+/// `attachments.poll_ids` expansion.
+/// Reference: https://docs.x.com/x-api/fundamentals/data-dictionary#poll
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Poll {
+    pub id: String,
+    pub options: Vec<PollOption>,
+    #[serde(default)]
+    pub duration_minutes: Option<u32>,
+    /// ISO 8601 timestamp at which voting closes
+    #[serde(default)]
+    pub end_datetime: Option<String>,
+    #[serde(default)]
+    pub voting_status: Option<PollVotingStatus>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PollOption {
+    /// 1-based
+    pub position: u32,
+    pub label: String,
+    #[serde(default)]
+    pub votes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PollVotingStatus {
+    Open,
+    Closed,
+    #[serde(other)]
+    Unknown,
+}
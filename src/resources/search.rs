@@ -67,6 +67,8 @@ impl SearchParams {
         }
     }
 
+    // Kept as `&Vec` so the public signature does not change under existing callers
+    #[allow(clippy::ptr_arg)]
     pub fn build_whitelist_query(usernames: &Vec<String>, keywords: Option<&str>) -> Self {
         // Create "from:user1 OR from:user2" string
         let user_query = usernames
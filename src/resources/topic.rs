@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// A topic (e.g. from Spaces or context annotations), returned in `includes.topics`.
+/// Reference: https://docs.x.com/x-api/fundamentals/data-dictionary#topic
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Topic {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
@@ -1,11 +1,16 @@
 use std::fmt::Debug;
 
 use crate::error::SdkResult;
+use crate::resources::Withheld;
+use crate::resources::entities::{TweetEntities, UrlImage};
 use crate::{client::TwitterClient, resources::TwitterApiResponse};
 use async_trait::async_trait;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
+/// A Tweet. Apart from `id` and `text`, fields are only present when requested
+/// through the matching `TweetField`.
+/// Reference: https://docs.x.com/x-api/fundamentals/data-dictionary#tweet
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Tweet {
     pub id: String,
@@ -16,6 +21,244 @@ pub struct Tweet {
     pub created_at: Option<String>,
     #[serde(default)]
     pub public_metrics: Option<TweetPublicMetrics>,
+    /// Retweeted, quoted or replied-to Tweets; expand with `TweetExpansion::ReferencedTweetsId`
+    #[serde(default)]
+    pub referenced_tweets: Option<Vec<ReferencedTweet>>,
+    #[serde(default)]
+    pub attachments: Option<TweetAttachments>,
+    #[serde(default)]
+    pub geo: Option<TweetGeo>,
+    /// Long-form article attached to the Tweet
+    #[serde(default)]
+    pub article: Option<TweetArticle>,
+    #[serde(default)]
+    pub card_uri: Option<String>,
+    #[serde(default)]
+    pub community_id: Option<String>,
+    #[serde(default)]
+    pub context_annotations: Option<Vec<ContextAnnotation>>,
+    /// Id of the Tweet that started the conversation this Tweet belongs to
+    #[serde(default)]
+    pub conversation_id: Option<String>,
+    /// `[start, end)` code point range of `text` that is meant to be displayed
+    #[serde(default)]
+    pub display_text_range: Option<(u32, u32)>,
+    #[serde(default)]
+    pub edit_controls: Option<EditControls>,
+    /// All versions of the Tweet, oldest first; the last one is the current version
+    #[serde(default)]
+    pub edit_history_tweet_ids: Option<Vec<String>>,
+    #[serde(default)]
+    pub entities: Option<TweetEntities>,
+    #[serde(default)]
+    pub in_reply_to_user_id: Option<String>,
+    /// BCP 47 language tag, `und` when undetermined
+    #[serde(default)]
+    pub lang: Option<String>,
+    #[serde(default)]
+    pub media_metadata: Option<Vec<MediaMetadata>>,
+    /// Only available to the author (user context)
+    #[serde(default)]
+    pub non_public_metrics: Option<TweetNonPublicMetrics>,
+    /// The full text of a Tweet longer than 280 characters; `text` is then truncated
+    #[serde(default)]
+    pub note_tweet: Option<NoteTweet>,
+    /// Only available to the author (user context)
+    #[serde(default)]
+    pub organic_metrics: Option<TweetEngagementMetrics>,
+    #[serde(default)]
+    pub possibly_sensitive: Option<bool>,
+    /// Only available to the author of a promoted Tweet (user context)
+    #[serde(default)]
+    pub promoted_metrics: Option<TweetEngagementMetrics>,
+    #[serde(default)]
+    pub reply_settings: Option<ReplySettings>,
+    #[serde(default)]
+    pub scopes: Option<TweetScopes>,
+    /// The client used to post the Tweet
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Sources X suggests for the claims in the Tweet
+    #[serde(default)]
+    pub suggested_source_links: Option<Vec<SuggestedSourceLink>>,
+    #[serde(default)]
+    pub withheld: Option<Withheld>,
+}
+
+impl Tweet {
+    /// The full text, taken from `note_tweet` for long Tweets.
+    pub fn full_text(&self) -> &str {
+        self.note_tweet
+            .as_ref()
+            .map_or(self.text.as_str(), |note| note.text.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ReferencedTweet {
+    #[serde(rename = "type")]
+    pub kind: ReferencedTweetKind,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferencedTweetKind {
+    Retweeted,
+    Quoted,
+    RepliedTo,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TweetAttachments {
+    /// Resolved in `includes.media` with `TweetExpansion::AttachmentsMediaKeys`
+    #[serde(default)]
+    pub media_keys: Option<Vec<String>>,
+    /// Resolved in `includes.polls` with `TweetExpansion::AttachmentsPollIds`
+    #[serde(default)]
+    pub poll_ids: Option<Vec<String>>,
+    #[serde(default)]
+    pub media_source_tweet_id: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TweetGeo {
+    /// Resolved in `includes.places` with `TweetExpansion::GeoPlaceId`
+    #[serde(default)]
+    pub place_id: Option<String>,
+    /// GeoJSON point, only present when the user shared their exact location
+    #[serde(default)]
+    pub coordinates: Option<TweetCoordinates>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TweetCoordinates {
+    /// Usually "Point"
+    #[serde(rename = "type")]
+    pub geo_type: String,
+    /// `[longitude, latitude]`
+    pub coordinates: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TweetArticle {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub preview_text: Option<String>,
+    /// Media key; expand with `TweetExpansion::ArticleCoverMedia`
+    #[serde(default)]
+    pub cover_media: Option<String>,
+    /// Media keys; expand with `TweetExpansion::ArticleMediaEntities`
+    #[serde(default)]
+    pub media_entities: Option<Vec<String>>,
+}
+
+/// What a Tweet is about, as inferred by X (domain "Brand", entity "Nike", ...)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ContextAnnotation {
+    pub domain: ContextAnnotationItem,
+    pub entity: ContextAnnotationItem,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ContextAnnotationItem {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EditControls {
+    pub edits_remaining: u32,
+    pub is_edit_eligible: bool,
+    /// ISO 8601 timestamp after which the Tweet can no longer be edited
+    pub editable_until: String,
+}
+
+/// A link that is not part of the text, so unlike `UrlEntity` it has no offsets
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SuggestedSourceLink {
+    pub url: String,
+    #[serde(default)]
+    pub expanded_url: Option<String>,
+    #[serde(default)]
+    pub display_url: Option<String>,
+    #[serde(default)]
+    pub unwound_url: Option<String>,
+    #[serde(default)]
+    pub status: Option<u16>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub images: Option<Vec<UrlImage>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MediaMetadata {
+    pub media_key: String,
+    #[serde(default)]
+    pub alt_text: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NoteTweet {
+    pub text: String,
+    #[serde(default)]
+    pub entities: Option<TweetEntities>,
+}
+
+/// Who can reply to a Tweet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReplySettings {
+    Everyone,
+    MentionedUsers,
+    Following,
+    Subscribers,
+    Verified,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TweetScopes {
+    /// Only visible to the author's followers (promoted-only Tweets)
+    #[serde(default)]
+    pub followers: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TweetNonPublicMetrics {
+    #[serde(default)]
+    pub impression_count: Option<u64>,
+    #[serde(default)]
+    pub url_link_clicks: Option<u64>,
+    #[serde(default)]
+    pub user_profile_clicks: Option<u64>,
+}
+
+/// `organic_metrics` and `promoted_metrics`
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TweetEngagementMetrics {
+    #[serde(default)]
+    pub impression_count: Option<u64>,
+    #[serde(default)]
+    pub like_count: Option<u64>,
+    #[serde(default)]
+    pub reply_count: Option<u64>,
+    #[serde(default)]
+    pub retweet_count: Option<u64>,
+    #[serde(default)]
+    pub url_link_clicks: Option<u64>,
+    #[serde(default)]
+    pub user_profile_clicks: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
@@ -24,7 +267,10 @@ pub struct TweetPublicMetrics {
     pub reply_count: u32,
     pub like_count: u32,
     pub quote_count: u32,
+    // Missing on older Tweets
+    #[serde(default)]
     pub impression_count: u32,
+    #[serde(default)]
     pub bookmark_count: u32,
 }
 
@@ -52,3 +298,161 @@ impl TweetApi for TweetHandler {
         self.client.request(Method::GET, &endpoint).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A v2 `GET /2/tweets/:id` response with every `TweetField` requested. The Tweet
+    // predates impression and bookmark counts, so X leaves them out of its metrics.
+    const FULL_TWEET: &str = r#"{
+      "data": {
+        "id": "1460323737035677698",
+        "text": "Introducing a new era for the Twitter Developer Platform! 📣 #TwitterAPI https://t.co/XVE1rZTc5W",
+        "author_id": "2244994945",
+        "created_at": "2021-11-15T19:08:05.000Z",
+        "conversation_id": "1460323737035677698",
+        "in_reply_to_user_id": "783214",
+        "lang": "en",
+        "source": "Twitter Web App",
+        "possibly_sensitive": false,
+        "reply_settings": "mentionedUsers",
+        "display_text_range": [0, 77],
+        "card_uri": "card://1460323736435893248",
+        "community_id": "1493446837214187523",
+        "edit_history_tweet_ids": ["1460323737035677698"],
+        "edit_controls": {
+          "edits_remaining": 5,
+          "is_edit_eligible": true,
+          "editable_until": "2021-11-15T20:08:05.000Z"
+        },
+        "note_tweet": {
+          "text": "Introducing a new era for the Twitter Developer Platform! This is the long form of the post.",
+          "entities": {
+            "hashtags": [{ "start": 58, "end": 69, "tag": "TwitterAPI" }]
+          }
+        },
+        "entities": {
+          "annotations": [
+            { "start": 42, "end": 48, "probability": 0.5625, "type": "Product", "normalized_text": "Twitter" }
+          ],
+          "hashtags": [{ "start": 60, "end": 71, "tag": "TwitterAPI" }],
+          "mentions": [{ "start": 0, "end": 10, "username": "XDevelopers", "id": "2244994945" }],
+          "urls": [
+            {
+              "start": 72,
+              "end": 95,
+              "url": "https://t.co/XVE1rZTc5W",
+              "expanded_url": "https://developer.twitter.com/en/docs/twitter-api",
+              "display_url": "developer.twitter.com/en/docs/twitte…",
+              "unwound_url": "https://developer.twitter.com/en/docs/twitter-api",
+              "status": 200,
+              "title": "Twitter API",
+              "images": [{ "url": "https://pbs.twimg.com/news_img/1.jpg", "width": 1200, "height": 630 }]
+            }
+          ]
+        },
+        "context_annotations": [
+          {
+            "domain": { "id": "46", "name": "Business Taxonomy", "description": "Categories within Brand Verticals" },
+            "entity": { "id": "1557697333571112960", "name": "Technology Business" }
+          }
+        ],
+        "attachments": { "media_keys": ["3_1460323733906919430"], "poll_ids": ["1199786642468413448"] },
+        "geo": {
+          "place_id": "01a9a39529b27f36",
+          "coordinates": { "type": "Point", "coordinates": [-73.99, 40.73] }
+        },
+        "referenced_tweets": [{ "type": "quoted", "id": "1460317402340999171" }],
+        "media_metadata": [{ "media_key": "3_1460323733906919430", "alt_text": "A launch banner" }],
+        "public_metrics": { "retweet_count": 628, "reply_count": 105, "like_count": 2561, "quote_count": 311 },
+        "non_public_metrics": { "impression_count": 95000, "url_link_clicks": 1200, "user_profile_clicks": 340 },
+        "organic_metrics": { "impression_count": 94000, "like_count": 2500, "reply_count": 100, "retweet_count": 600 },
+        "promoted_metrics": { "impression_count": 1000, "like_count": 61 },
+        "scopes": { "followers": false },
+        "withheld": { "copyright": false, "country_codes": ["DE", "FR"], "scope": "tweet" },
+        "article": { "title": "The new platform", "preview_text": "What's new", "cover_media": "3_1460323733906919430" },
+        "suggested_source_links": [
+          {
+            "url": "https://t.co/Zl8B3x2Ncz",
+            "expanded_url": "https://developer.x.com/en/docs/x-api",
+            "display_url": "developer.x.com/en/docs/x-api",
+            "title": "X API"
+          }
+        ]
+      }
+    }"#;
+
+    #[test]
+    fn deserializes_every_tweet_field() {
+        let response: TwitterApiResponse<Tweet> = serde_json::from_str(FULL_TWEET).unwrap();
+        let tweet = response.data.unwrap();
+
+        assert_eq!(tweet.display_text_range, Some((0, 77)));
+        assert_eq!(tweet.reply_settings, Some(ReplySettings::MentionedUsers));
+        assert!(tweet.full_text().ends_with("long form of the post."));
+        assert_ne!(tweet.full_text(), tweet.text);
+
+        let edit_controls = tweet.edit_controls.unwrap();
+        assert_eq!(edit_controls.edits_remaining, 5);
+        assert!(edit_controls.is_edit_eligible);
+
+        let annotation = &tweet.context_annotations.unwrap()[0];
+        assert_eq!(annotation.domain.id, "46");
+        assert_eq!(
+            annotation.entity.name.as_deref(),
+            Some("Technology Business")
+        );
+        assert_eq!(annotation.entity.description, None);
+
+        let withheld = tweet.withheld.unwrap();
+        assert_eq!(withheld.copyright, Some(false));
+        assert_eq!(withheld.country_codes, ["DE", "FR"]);
+
+        let metrics = tweet.public_metrics.unwrap();
+        assert_eq!(metrics.like_count, 2561);
+        assert_eq!(metrics.impression_count, 0);
+        assert_eq!(metrics.bookmark_count, 0);
+
+        let entities = tweet.entities.unwrap();
+        assert_eq!(entities.mentions.unwrap()[0].username, "XDevelopers");
+        assert_eq!(
+            entities.urls.unwrap()[0].images.as_ref().unwrap()[0].width,
+            Some(1200)
+        );
+        assert_eq!(
+            entities.annotations.unwrap()[0].annotation_type.as_deref(),
+            Some("Product")
+        );
+
+        assert_eq!(
+            tweet.referenced_tweets.unwrap()[0].kind,
+            ReferencedTweetKind::Quoted
+        );
+        assert_eq!(
+            tweet.geo.unwrap().coordinates.unwrap().coordinates,
+            [-73.99, 40.73]
+        );
+        assert_eq!(
+            tweet.non_public_metrics.unwrap().impression_count,
+            Some(95000)
+        );
+        assert_eq!(tweet.promoted_metrics.unwrap().retweet_count, None);
+        assert!(!tweet.scopes.unwrap().followers);
+
+        let source = &tweet.suggested_source_links.unwrap()[0];
+        assert_eq!(source.url, "https://t.co/Zl8B3x2Ncz");
+        assert_eq!(source.title.as_deref(), Some("X API"));
+        assert!(source.images.is_none());
+    }
+
+    #[test]
+    fn unknown_reply_settings_do_not_fail() {
+        let tweet: Tweet =
+            serde_json::from_str(r#"{"id":"1","text":"hi","reply_settings":"somethingNew"}"#)
+                .unwrap();
+
+        assert_eq!(tweet.reply_settings, Some(ReplySettings::Unknown));
+        assert_eq!(tweet.full_text(), "hi");
+    }
+}
@@ -1,17 +1,129 @@
 use std::fmt::Debug;
 
 use crate::error::SdkResult;
+use crate::resources::Withheld;
+use crate::resources::entities::UserEntities;
 use crate::{client::TwitterClient, resources::TwitterApiResponse};
 use async_trait::async_trait;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
+/// A user. Apart from `id`, `name` and `username`, fields are only present when
+/// requested through the matching `UserField`.
+/// Reference: https://docs.x.com/x-api/fundamentals/data-dictionary#user
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct User {
     pub id: String,
     pub name: String,
     pub username: String,
     pub public_metrics: Option<UserPublicMetrics>,
+    /// The organization the account is affiliated with (its badge)
+    #[serde(default)]
+    pub affiliation: Option<UserAffiliation>,
+    /// Only returned for the authenticated user, with the `users.email` scope
+    #[serde(default)]
+    pub confirmed_email: Option<String>,
+    /// How the authenticated user relates to this one
+    #[serde(default)]
+    pub connection_status: Option<Vec<ConnectionStatus>>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// The bio
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub entities: Option<UserEntities>,
+    #[serde(default)]
+    pub is_identity_verified: Option<bool>,
+    /// Free-form, not necessarily a real place
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub most_recent_tweet_id: Option<String>,
+    #[serde(default)]
+    pub parody: Option<bool>,
+    #[serde(default)]
+    pub pinned_tweet_id: Option<String>,
+    #[serde(default)]
+    pub profile_banner_url: Option<String>,
+    #[serde(default)]
+    pub profile_image_url: Option<String>,
+    /// Tweets are only visible to approved followers
+    #[serde(default)]
+    pub protected: Option<bool>,
+    #[serde(default)]
+    pub receives_your_dm: Option<bool>,
+    #[serde(default)]
+    pub subscription: Option<UserSubscription>,
+    #[serde(default)]
+    pub subscription_type: Option<SubscriptionType>,
+    /// The website in the profile, as a t.co link (see `entities.url` for the expanded one)
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub verified: Option<bool>,
+    #[serde(default)]
+    pub verified_followers_count: Option<u64>,
+    #[serde(default)]
+    pub verified_type: Option<VerifiedType>,
+    #[serde(default)]
+    pub withheld: Option<Withheld>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct UserAffiliation {
+    #[serde(default)]
+    pub badge_url: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    /// The affiliated organization's account
+    #[serde(default)]
+    pub user_id: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionStatus {
+    FollowRequestReceived,
+    FollowRequestSent,
+    Blocking,
+    FollowedBy,
+    Following,
+    Muting,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct UserSubscription {
+    /// Whether this user subscribes to the authenticated user
+    #[serde(default)]
+    pub subscribes_to_you: bool,
+}
+
+/// The user's X Premium tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubscriptionType {
+    Basic,
+    Premium,
+    PremiumPlus,
+    None,
+    #[serde(other)]
+    Unknown,
+}
+
+/// The kind of checkmark shown on the profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VerifiedType {
+    Blue,
+    Business,
+    Government,
+    None,
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
@@ -61,3 +173,135 @@ impl UserApi for UserHandler {
         self.client.request(Method::GET, &endpoint).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A v2 `GET /2/users/:id` response with every `UserField` requested, as seen by
+    // another user (no `confirmed_email`)
+    const FULL_USER: &str = r#"{
+      "data": {
+        "id": "2244994945",
+        "name": "Developers",
+        "username": "XDevelopers",
+        "created_at": "2013-12-14T04:35:55.000Z",
+        "description": "The voice of the #XAPI team. Questions? Ask @XDevelopers or visit https://t.co/8IkCzCDr19",
+        "location": "127.0.0.1",
+        "url": "https://t.co/3ZX3TNiZCY",
+        "profile_image_url": "https://pbs.twimg.com/profile_images/1/avatar_normal.jpg",
+        "profile_banner_url": "https://pbs.twimg.com/profile_banners/2244994945/1",
+        "pinned_tweet_id": "1460323737035677698",
+        "most_recent_tweet_id": "1852000000000000000",
+        "protected": false,
+        "verified": true,
+        "verified_type": "business",
+        "verified_followers_count": 12000,
+        "is_identity_verified": false,
+        "parody": false,
+        "receives_your_dm": true,
+        "subscription_type": "PremiumPlus",
+        "subscription": { "subscribes_to_you": false },
+        "connection_status": ["following", "followed_by", "follow_request_sent"],
+        "affiliation": {
+          "badge_url": "https://pbs.twimg.com/profile_images/2/x_normal.jpg",
+          "description": "X",
+          "url": "https://x.com/X",
+          "user_id": ["783214"]
+        },
+        "withheld": { "country_codes": ["DE"], "scope": "user" },
+        "public_metrics": {
+          "followers_count": 583423,
+          "following_count": 2048,
+          "tweet_count": 14052,
+          "listed_count": 1672,
+          "like_count": 2100,
+          "media_count": 310
+        },
+        "entities": {
+          "url": {
+            "urls": [
+              {
+                "start": 0,
+                "end": 23,
+                "url": "https://t.co/3ZX3TNiZCY",
+                "expanded_url": "https://developer.x.com",
+                "display_url": "developer.x.com"
+              }
+            ]
+          },
+          "description": {
+            "hashtags": [{ "start": 17, "end": 22, "tag": "XAPI" }],
+            "mentions": [{ "start": 45, "end": 57, "username": "XDevelopers" }],
+            "urls": [
+              {
+                "start": 67,
+                "end": 90,
+                "url": "https://t.co/8IkCzCDr19",
+                "expanded_url": "https://devcommunity.x.com",
+                "display_url": "devcommunity.x.com"
+              }
+            ]
+          }
+        }
+      }
+    }"#;
+
+    #[test]
+    fn deserializes_every_user_field() {
+        let response: TwitterApiResponse<User> = serde_json::from_str(FULL_USER).unwrap();
+        let user = response.data.unwrap();
+
+        assert_eq!(user.subscription_type, Some(SubscriptionType::PremiumPlus));
+        assert_eq!(user.verified_type, Some(VerifiedType::Business));
+        assert_eq!(
+            user.connection_status.unwrap(),
+            [
+                ConnectionStatus::Following,
+                ConnectionStatus::FollowedBy,
+                ConnectionStatus::FollowRequestSent
+            ]
+        );
+        assert_eq!(user.affiliation.unwrap().user_id.unwrap(), ["783214"]);
+        assert!(!user.subscription.unwrap().subscribes_to_you);
+        assert_eq!(user.withheld.unwrap().country_codes, ["DE"]);
+        assert_eq!(user.public_metrics.unwrap().media_count, Some(310));
+        assert_eq!(user.confirmed_email, None);
+
+        let entities = user.entities.unwrap();
+        assert_eq!(
+            entities.url.unwrap().urls.unwrap()[0]
+                .expanded_url
+                .as_deref(),
+            Some("https://developer.x.com")
+        );
+
+        let description = entities.description.unwrap();
+        assert_eq!(description.hashtags.unwrap()[0].tag, "XAPI");
+        assert_eq!(description.mentions.unwrap()[0].id, None);
+        assert_eq!(description.urls.unwrap()[0].end, 90);
+        assert!(description.cashtags.is_none());
+    }
+
+    #[test]
+    fn unknown_enum_values_do_not_fail() {
+        let user: User = serde_json::from_str(
+            r#"{
+              "id": "1",
+              "name": "n",
+              "username": "u",
+              "subscription_type": "SomethingNew",
+              "verified_type": "somethingNew",
+              "connection_status": ["blocking", "somethingNew"]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(user.subscription_type, Some(SubscriptionType::Unknown));
+        assert_eq!(user.verified_type, Some(VerifiedType::Unknown));
+        assert_eq!(
+            user.connection_status.unwrap(),
+            [ConnectionStatus::Blocking, ConnectionStatus::Unknown]
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+
+use crate::client::RawResponse;
+use crate::error::{SdkError, SdkResult, is_usage_capped};
+use crate::rate_limit::RateLimitInfo;
+
+/// When and how `TwitterClient` retries a failed request. Applies to every handler.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on every further attempt
+    pub base_delay: Duration,
+    /// Upper bound for the exponential backoff
+    pub max_delay: Duration,
+    /// Randomize each delay between half and the full backoff, so that many
+    /// clients failing together do not retry in lockstep
+    pub jitter: bool,
+    pub retryable_statuses: Vec<u16>,
+    /// Wait for `Retry-After` / `x-rate-limit-reset` instead of the backoff when the
+    /// API sends them (429, 503)
+    pub respect_rate_limit_reset: bool,
+    /// Give up instead of waiting longer than this for a rate-limit reset
+    pub max_rate_limit_wait: Duration,
+    /// Also retry non-idempotent requests (POST, PATCH). Off by default, since a
+    /// request that timed out may still have been applied (e.g. a duplicate post).
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retryable_statuses: vec![429, 500, 502, 503, 504],
+            respect_rate_limit_reset: true,
+            max_rate_limit_wait: Duration::from_secs(15 * 60),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_backoff(mut self, base_delay: Duration, max_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn with_retryable_statuses(mut self, statuses: impl Into<Vec<u16>>) -> Self {
+        self.retryable_statuses = statuses.into();
+        self
+    }
+
+    pub fn with_rate_limit_reset(mut self, respect: bool, max_wait: Duration) -> Self {
+        self.respect_rate_limit_reset = respect;
+        self.max_rate_limit_wait = max_wait;
+        self
+    }
+
+    pub fn with_retry_non_idempotent(mut self, retry: bool) -> Self {
+        self.retry_non_idempotent = retry;
+        self
+    }
+
+    pub fn allows(&self, method: &Method) -> bool {
+        self.retry_non_idempotent || is_idempotent(method)
+    }
+
+    /// Decides whether `attempt` (1-based) should be followed by another one, and after
+    /// how long. `None` means the outcome is final.
+    pub(crate) fn next_delay(
+        &self,
+        outcome: &SdkResult<RawResponse>,
+        attempt: u32,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        match outcome {
+            Err(SdkError::Http(e)) if e.is_connect() || e.is_timeout() || e.is_request() => {
+                Some(self.backoff(attempt))
+            }
+            Err(_) => None,
+            Ok(response) => {
+                let status = response.status;
+                if !self.retryable_statuses.contains(&status.as_u16()) {
+                    return None;
+                }
+                // The monthly usage cap answers 429 too, but no wait will lift it
+                if status == StatusCode::TOO_MANY_REQUESTS && is_usage_capped(&response.body) {
+                    return None;
+                }
+
+                let server_delay = self
+                    .respect_rate_limit_reset
+                    .then(|| server_requested_delay(status, &response.headers))
+                    .flatten();
+
+                match server_delay {
+                    Some(delay) if delay > self.max_rate_limit_wait => None,
+                    Some(delay) => Some(delay),
+                    None => Some(self.backoff(attempt)),
+                }
+            }
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+
+        if self.jitter && !delay.is_zero() {
+            let half = delay / 2;
+            half + rand::thread_rng().gen_range(Duration::ZERO..=half)
+        } else {
+            delay
+        }
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS | Method::TRACE
+    )
+}
+
+/// `Retry-After` (seconds) wins; for 429s the rate-limit reset is used otherwise, or the
+/// 24-hour reset once the per-user daily cap is used up (it outlasts the window)
+fn server_requested_delay(status: StatusCode, headers: &HeaderMap) -> Option<Duration> {
+    let retry_after = headers
+        .get(RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs);
+
+    if retry_after.is_some() || status != StatusCode::TOO_MANY_REQUESTS {
+        return retry_after;
+    }
+
+    let info = RateLimitInfo::from_headers(headers)?;
+    let reset_in = if info.user_remaining_24h == Some(0) {
+        info.user_reset_24h_in()
+    } else {
+        info.reset_in()
+    };
+
+    // Reset timestamps have one-second granularity
+    reset_in.map(|wait| wait + Duration::from_secs(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::*;
+
+    fn response(
+        status: u16,
+        headers: &[(&'static str, String)],
+        body: &str,
+    ) -> SdkResult<RawResponse> {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.insert(*name, value.parse().unwrap());
+        }
+
+        Ok(RawResponse {
+            status: StatusCode::from_u16(status).unwrap(),
+            headers: map,
+            body: body.to_string(),
+        })
+    }
+
+    fn in_secs(secs: u64) -> String {
+        (SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + secs)
+            .to_string()
+    }
+
+    fn no_jitter() -> RetryPolicy {
+        RetryPolicy::default()
+            .with_max_attempts(10)
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .with_jitter(false)
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = no_jitter();
+        let delays: Vec<_> = (1..=6).map(|attempt| policy.backoff(attempt)).collect();
+
+        assert_eq!(
+            delays,
+            [100, 200, 400, 800, 1000, 1000].map(Duration::from_millis)
+        );
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn jitter_stays_between_half_and_full_backoff() {
+        let policy = no_jitter().with_jitter(true);
+
+        for _ in 0..100 {
+            let delay = policy.backoff(3);
+            assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400));
+        }
+    }
+
+    #[test]
+    fn stops_after_max_attempts() {
+        let policy = no_jitter().with_max_attempts(2);
+
+        assert!(policy.next_delay(&response(503, &[], ""), 1).is_some());
+        assert_eq!(policy.next_delay(&response(503, &[], ""), 2), None);
+        assert_eq!(policy.next_delay(&response(400, &[], ""), 1), None);
+    }
+
+    #[test]
+    fn honors_retry_after_and_rate_limit_reset() {
+        let policy = no_jitter();
+
+        let retry_after = response(503, &[("retry-after", "7".to_string())], "");
+        assert_eq!(
+            policy.next_delay(&retry_after, 1),
+            Some(Duration::from_secs(7))
+        );
+
+        let reset = response(429, &[("x-rate-limit-reset", in_secs(30))], "");
+        let delay = policy.next_delay(&reset, 1).unwrap();
+        assert!(delay > Duration::from_secs(29) && delay <= Duration::from_secs(31));
+
+        let too_long = response(429, &[("x-rate-limit-reset", in_secs(3600))], "");
+        assert_eq!(policy.next_delay(&too_long, 1), None);
+    }
+
+    #[test]
+    fn gives_up_on_exhausted_daily_cap() {
+        let policy = no_jitter();
+        let daily_cap = response(
+            429,
+            &[
+                ("x-rate-limit-reset", in_secs(60)),
+                ("x-user-limit-24hour-remaining", "0".to_string()),
+                ("x-user-limit-24hour-reset", in_secs(20 * 3600)),
+            ],
+            "",
+        );
+
+        assert_eq!(policy.next_delay(&daily_cap, 1), None);
+    }
+
+    #[test]
+    fn does_not_retry_non_idempotent_requests_by_default() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.allows(&Method::GET));
+        assert!(policy.allows(&Method::DELETE));
+        assert!(!policy.allows(&Method::POST));
+        assert!(!policy.allows(&Method::PATCH));
+        assert!(policy.with_retry_non_idempotent(true).allows(&Method::POST));
+    }
+
+    #[test]
+    fn does_not_retry_usage_capped_429() {
+        let policy = RetryPolicy::default();
+        let capped = r#"{"title":"UsageCapExceeded","detail":"Usage cap exceeded: Monthly product cap","type":"https://api.twitter.com/2/problems/usage-capped"}"#;
+
+        assert_eq!(policy.next_delay(&response(429, &[], capped), 1), None);
+        assert!(policy.next_delay(&response(429, &[], ""), 1).is_some());
+    }
+}
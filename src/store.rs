@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{Mutex, RwLock};
+
+use crate::auth::TwitterToken;
+use crate::error::{SdkError, SdkResult};
+
+/// Persists `TwitterToken`s per connected account, so `RusxGateway::for_account`
+/// can resolve (and keep refreshed) a client for any of them.
+#[cfg_attr(feature = "testing", mockall::automock)]
+#[async_trait]
+pub trait TokenStore: Debug + Send + Sync {
+    async fn get(&self, account_id: &str) -> SdkResult<Option<TwitterToken>>;
+    async fn put(&self, account_id: &str, token: TwitterToken) -> SdkResult<()>;
+    async fn delete(&self, account_id: &str) -> SdkResult<()>;
+}
+
+/// Process-local store, handy for tests and single-instance tools.
+#[derive(Debug, Default)]
+pub struct InMemoryTokenStore {
+    tokens: RwLock<HashMap<String, TwitterToken>>,
+}
+
+impl InMemoryTokenStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TokenStore for InMemoryTokenStore {
+    async fn get(&self, account_id: &str) -> SdkResult<Option<TwitterToken>> {
+        Ok(self.tokens.read().await.get(account_id).cloned())
+    }
+
+    async fn put(&self, account_id: &str, token: TwitterToken) -> SdkResult<()> {
+        self.tokens
+            .write()
+            .await
+            .insert(account_id.to_string(), token);
+        Ok(())
+    }
+
+    async fn delete(&self, account_id: &str) -> SdkResult<()> {
+        self.tokens.write().await.remove(account_id);
+        Ok(())
+    }
+}
+
+/// Keeps all tokens in a single JSON object (`{ "<account_id>": TwitterToken }`).
+/// Writes go to a temporary file that is renamed over the original, so a crash
+/// never leaves a half-written store behind.
+#[derive(Debug)]
+pub struct FileTokenStore {
+    path: PathBuf,
+    // Serializes read-modify-write cycles within this process
+    lock: Mutex<()>,
+}
+
+impl FileTokenStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn load(&self) -> SdkResult<HashMap<String, TwitterToken>> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) if bytes.is_empty() => Ok(HashMap::new()),
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(store_error(&self.path, e)),
+        }
+    }
+
+    async fn save(&self, tokens: &HashMap<String, TwitterToken>) -> SdkResult<()> {
+        let bytes = serde_json::to_vec_pretty(tokens)?;
+        let tmp = self.path.with_extension("tmp");
+
+        write_private(&tmp, &bytes)
+            .await
+            .map_err(|e| store_error(&tmp, e))?;
+        tokio::fs::rename(&tmp, &self.path)
+            .await
+            .map_err(|e| store_error(&self.path, e))
+    }
+}
+
+#[async_trait]
+impl TokenStore for FileTokenStore {
+    async fn get(&self, account_id: &str) -> SdkResult<Option<TwitterToken>> {
+        let _guard = self.lock.lock().await;
+        Ok(self.load().await?.remove(account_id))
+    }
+
+    async fn put(&self, account_id: &str, token: TwitterToken) -> SdkResult<()> {
+        let _guard = self.lock.lock().await;
+        let mut tokens = self.load().await?;
+        tokens.insert(account_id.to_string(), token);
+        self.save(&tokens).await
+    }
+
+    async fn delete(&self, account_id: &str) -> SdkResult<()> {
+        let _guard = self.lock.lock().await;
+        let mut tokens = self.load().await?;
+        if tokens.remove(account_id).is_some() {
+            self.save(&tokens).await?;
+        }
+        Ok(())
+    }
+}
+
+fn store_error(path: &Path, e: std::io::Error) -> SdkError {
+    SdkError::TokenStore(format!("{}: {}", path.display(), e))
+}
+
+// The file holds live credentials, so it is only readable by its owner
+async fn write_private(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    // A leftover temp file would keep its old permissions
+    match tokio::fs::remove_file(path).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file = options.open(path).await?;
+    file.write_all(bytes).await?;
+    file.sync_all().await
+}